@@ -0,0 +1,70 @@
+/// Tightest spacing between two requests of a burst.
+pub const MIN_BURST_SPACING_MS: i64 = 2;
+
+/// Send offsets for a burst, relative to the target send instant (release minus `ms_offset`).
+#[derive(Clone)]
+pub struct BurstPlan {
+    pub offsets_ms: Vec<i64>,
+    pub spacing_ms: i64,
+    /// Half-width of the interval the burst spans around the target.
+    pub half_span_ms: i64,
+}
+
+impl BurstPlan {
+    pub fn first_offset_ms(&self) -> i64 {
+        self.offsets_ms.first().copied().unwrap_or(0)
+    }
+}
+
+/// Spreads `count` requests symmetrically around the target so the burst spans at least the
+/// 95% clock uncertainty, or sits at the minimum spacing when the clock is well known.
+pub fn plan(count: usize, uncertainty_95_ms: i64) -> BurstPlan {
+    if count <= 1 {
+        return BurstPlan {
+            offsets_ms: vec![0; count],
+            spacing_ms: 0,
+            half_span_ms: 0,
+        };
+    }
+    let gaps = count as i64 - 1;
+    let spacing_ms = MIN_BURST_SPACING_MS.max((2 * uncertainty_95_ms.max(0) + gaps - 1) / gaps);
+    let span = spacing_ms * gaps;
+    BurstPlan {
+        offsets_ms: (0..count as i64).map(|i| i * spacing_ms - span / 2).collect(),
+        spacing_ms,
+        half_span_ms: span / 2,
+    }
+}
+
+/// Probability that the true release falls inside the burst span, modelling the clock error as
+/// a normal distribution whose 95% bound is `uncertainty_95_ms`.
+pub fn coverage_probability(half_span_ms: i64, uncertainty_95_ms: i64) -> f64 {
+    if uncertainty_95_ms <= 0 {
+        return 1.0;
+    }
+    let sigma = uncertainty_95_ms as f64 / 1.96;
+    erf(half_span_ms as f64 / (sigma * std::f64::consts::SQRT_2))
+}
+
+pub fn coverage_label(plan: &BurstPlan, uncertainty_95_ms: i64) -> String {
+    if plan.offsets_ms.len() <= 1 {
+        return format!("single request at the target, clock ±{}ms", uncertainty_95_ms);
+    }
+    format!(
+        "covers release ±{}ms at {:.0}% ({} requests, {}ms apart, clock ±{}ms)",
+        plan.half_span_ms,
+        coverage_probability(plan.half_span_ms, uncertainty_95_ms) * 100.0,
+        plan.offsets_ms.len(),
+        plan.spacing_ms,
+        uncertainty_95_ms
+    )
+}
+
+/// Abramowitz-Stegun 7.1.26, accurate to ~1.5e-7.
+pub fn erf(x: f64) -> f64 {
+    let sign = if x < 0.0 { -1.0 } else { 1.0 };
+    let x = x.abs();
+    let t = 1.0 / (1.0 + 0.3275911 * x);
+    let poly = t * (0.254829592 + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    sign * (1.0 - poly * (-x * x).exp())
+}
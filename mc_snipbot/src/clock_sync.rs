@@ -0,0 +1,568 @@
+use crate::logging::{Category, LogEvent, LogSink};
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+
+/// Assumed error of an NTP-disciplined system clock, used when no better bound is known.
+const SYSTEM_CLOCK_UNCERTAINTY: Duration = Duration::from_millis(50);
+/// Time APIs queried when none are configured.
+pub const DEFAULT_TIME_SOURCES: [&str; 1] = ["https://api.frankfurter.app/latest"];
+/// No sync is started or applied this close to the first request; the offset is frozen.
+pub const FREEZE_BEFORE_MS: i64 = 10_000;
+/// Longest one source can take to answer (the time client's request timeout).
+const SYNC_TIMEOUT_MS: i64 = 6_000;
+/// How fast an estimate is assumed to go stale when deciding whether a new sample is better.
+const DRIFT_ALLOWANCE_PPM: f64 = 50.0;
+/// Sync samples kept for the drift fit.
+const MAX_SAMPLES: usize = 64;
+/// Shortest span of samples a drift rate is fitted over.
+const MIN_DRIFT_SPAN: Duration = Duration::from_secs(120);
+/// Fits beyond this are treated as bad samples rather than a real oscillator.
+const MAX_PLAUSIBLE_DRIFT_PPM: f64 = 100.0;
+/// A fitted drift is only applied when the slope's standard error is at most this...
+const MAX_DRIFT_SE_PPM: f64 = 3.0;
+/// ...and the slope is this many standard errors away from zero.
+const DRIFT_SIGNIFICANCE: f64 = 3.0;
+
+/// One sync round's result.
+#[derive(Clone)]
+pub struct TimeSync {
+    /// Server time at `taken`: the reported time plus half the round trip.
+    pub time: DateTime<Utc>,
+    pub latency: Duration,
+    pub source: String,
+    /// 95% bound on the error of `time`: half the round trip plus the source's resolution.
+    pub uncertainty: Duration,
+    /// Monotonic instant the response arrived, which `time` describes.
+    pub taken: Instant,
+}
+
+impl TimeSync {
+    /// `time` carried forward (or back) to `at`, in unix ms.
+    fn time_at_ms(&self, at: Instant) -> i64 {
+        let shift_ms = match at.checked_duration_since(self.taken) {
+            Some(later) => later.as_millis() as i64,
+            None => -(self.taken.duration_since(at).as_millis() as i64),
+        };
+        self.time.timestamp_millis() + shift_ms
+    }
+}
+
+/// Server time anchored to a monotonic instant, so reading it never touches the network.
+#[derive(Clone)]
+pub struct ClockEstimate {
+    anchor_ms: i64,
+    anchor: Instant,
+    /// System clock minus synced time at the anchor.
+    system_offset_ms: i64,
+    pub uncertainty: Duration,
+    pub source: String,
+    /// Rate the local monotonic clock runs slow relative to the server, in ppm.
+    pub drift_ppm: f64,
+    /// Standard error of `drift_ppm`; zero when no drift is applied.
+    pub drift_se_ppm: f64,
+}
+
+impl ClockEstimate {
+    pub fn from_sync(sync: &TimeSync) -> Self {
+        let anchor_ms = sync.time.timestamp_millis();
+        Self {
+            anchor_ms,
+            anchor: sync.taken,
+            system_offset_ms: Utc::now().timestamp_millis() - sync.taken.elapsed().as_millis() as i64 - anchor_ms,
+            uncertainty: sync.uncertainty,
+            source: sync.source.clone(),
+            drift_ppm: 0.0,
+            drift_se_ppm: 0.0,
+        }
+    }
+
+    pub fn system() -> Self {
+        Self {
+            anchor_ms: Utc::now().timestamp_millis(),
+            anchor: Instant::now(),
+            system_offset_ms: 0,
+            uncertainty: SYSTEM_CLOCK_UNCERTAINTY,
+            source: "system".to_string(),
+            drift_ppm: 0.0,
+            drift_se_ppm: 0.0,
+        }
+    }
+
+    pub fn is_synced(&self) -> bool {
+        self.source != "system"
+    }
+
+    /// Current server time in unix ms, projected from the anchor with the drift rate applied.
+    pub fn now_ms(&self) -> i64 {
+        let elapsed_ms = self.anchor.elapsed().as_secs_f64() * 1000.0;
+        self.anchor_ms + (elapsed_ms * (1.0 + self.drift_ppm / 1e6)).round() as i64
+    }
+
+    /// Synced time minus system time, for mapping system clock readings onto server time.
+    pub fn clock_offset_ms(&self) -> i64 {
+        -self.system_offset_ms
+    }
+
+    pub fn age(&self) -> Duration {
+        self.anchor.elapsed()
+    }
+
+    /// Uncertainty grown since the anchor by the drift allowance plus the 95% error of the
+    /// applied drift rate.
+    pub fn aged_uncertainty(&self) -> Duration {
+        let ppm = DRIFT_ALLOWANCE_PPM + 2.0 * self.drift_se_ppm;
+        let drift_ms = self.anchor.elapsed().as_secs_f64() * ppm / 1000.0;
+        self.uncertainty + Duration::from_secs_f64(drift_ms / 1000.0)
+    }
+}
+
+/// Slope of a drift fit and its standard error.
+#[derive(Clone, Copy)]
+pub struct DriftFit {
+    pub ppm: f64,
+    pub se_ppm: f64,
+}
+
+impl DriftFit {
+    /// Plausible, well constrained and clearly away from zero; anything else is noise.
+    pub fn is_significant(&self) -> bool {
+        self.ppm.abs() <= MAX_PLAUSIBLE_DRIFT_PPM
+            && self.se_ppm <= MAX_DRIFT_SE_PPM
+            && self.ppm.abs() >= DRIFT_SIGNIFICANCE * self.se_ppm
+    }
+}
+
+struct SyncSample {
+    at: Instant,
+    server_ms: i64,
+    uncertainty_ms: f64,
+}
+
+/// Sync samples collected over the app's lifetime and the estimate derived from them.
+/// Shared between the GUI and missions, so syncs made while waiting for a drop feed the drift fit.
+pub struct ClockTracker {
+    samples: VecDeque<SyncSample>,
+    pub estimate: ClockEstimate,
+    /// Latest fit, once the samples span `MIN_DRIFT_SPAN`; only applied when significant.
+    pub drift: Option<DriftFit>,
+}
+
+impl Default for ClockTracker {
+    fn default() -> Self {
+        Self {
+            samples: VecDeque::new(),
+            estimate: ClockEstimate::system(),
+            drift: None,
+        }
+    }
+}
+
+impl ClockTracker {
+    /// Records a sync, refits the drift and adopts the sample when it beats the current
+    /// estimate. Returns whether the estimate was replaced.
+    pub fn add(&mut self, sync: &TimeSync) -> bool {
+        if self.samples.len() == MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(SyncSample {
+            at: sync.taken,
+            server_ms: sync.time.timestamp_millis(),
+            uncertainty_ms: (sync.uncertainty.as_secs_f64() * 1000.0).max(1.0),
+        });
+        self.drift = self.fit_drift();
+
+        let candidate = ClockEstimate::from_sync(sync);
+        let adopt = !self.estimate.is_synced() || candidate.uncertainty < self.estimate.aged_uncertainty();
+        if adopt {
+            self.estimate = candidate;
+        }
+        let applied = self.drift.filter(DriftFit::is_significant);
+        self.estimate.drift_ppm = applied.map_or(0.0, |fit| fit.ppm);
+        self.estimate.drift_se_ppm = applied.map_or(0.0, |fit| fit.se_ppm);
+        adopt
+    }
+
+    /// The drift rate in use, if the fit is significant.
+    pub fn drift_ppm(&self) -> Option<f64> {
+        self.drift.filter(DriftFit::is_significant).map(|fit| fit.ppm)
+    }
+
+    pub fn sample_count(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn sample_span(&self) -> Duration {
+        match (self.samples.front(), self.samples.back()) {
+            (Some(first), Some(last)) => last.at.duration_since(first.at),
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// Weighted least-squares slope of (server time - monotonic time) over monotonic time,
+    /// weighting each sample by the inverse square of its uncertainty. The standard error is
+    /// scaled by the residuals, so scatter beyond the stated uncertainties widens it.
+    fn fit_drift(&self) -> Option<DriftFit> {
+        if self.samples.len() < 3 || self.sample_span() < MIN_DRIFT_SPAN {
+            return None;
+        }
+        let base = &self.samples[0];
+        let points: Vec<(f64, f64, f64)> = self.samples.iter()
+            .map(|sample| {
+                let x = sample.at.duration_since(base.at).as_secs_f64() * 1000.0;
+                let y = (sample.server_ms - base.server_ms) as f64 - x;
+                (x, y, 1.0 / (sample.uncertainty_ms * sample.uncertainty_ms))
+            })
+            .collect();
+        let weight: f64 = points.iter().map(|(_, _, w)| w).sum();
+        let mean_x = points.iter().map(|(x, _, w)| x * w).sum::<f64>() / weight;
+        let mean_y = points.iter().map(|(_, y, w)| y * w).sum::<f64>() / weight;
+        let sxx: f64 = points.iter().map(|(x, _, w)| w * (x - mean_x).powi(2)).sum();
+        let sxy: f64 = points.iter().map(|(x, y, w)| w * (x - mean_x) * (y - mean_y)).sum();
+        if sxx <= 0.0 {
+            return None;
+        }
+        let slope = sxy / sxx;
+        let residuals: f64 = points.iter()
+            .map(|(x, y, w)| w * (y - mean_y - slope * (x - mean_x)).powi(2))
+            .sum();
+        let scale = residuals / (points.len() - 2) as f64;
+        Some(DriftFit {
+            ppm: slope * 1e6,
+            se_ppm: (scale / sxx).sqrt() * 1e6,
+        })
+    }
+}
+
+/// Longest a sync round can take: the sources are queried one after another.
+fn round_budget_ms(source_count: usize) -> i64 {
+    SYNC_TIMEOUT_MS * source_count.max(1) as i64
+}
+
+/// Whether a sync round over `source_count` sources started now finishes before the freeze.
+pub fn sync_allowed(remaining_ms: i64, source_count: usize) -> bool {
+    remaining_ms > FREEZE_BEFORE_MS + round_budget_ms(source_count)
+}
+
+/// Pause before the next sync round, or `None` once no round may be started any more.
+fn next_sync_in(remaining_ms: i64, source_count: usize) -> Option<Duration> {
+    if !sync_allowed(remaining_ms, source_count) {
+        None
+    } else if remaining_ms < 30_000 {
+        Some(Duration::from_secs(5))
+    } else if remaining_ms < 120_000 {
+        Some(Duration::from_secs(15))
+    } else {
+        Some(Duration::from_secs(45))
+    }
+}
+
+/// Runs one sync round over `sources`. Fails when every source failed and only the system
+/// clock is left.
+pub async fn sync_once(logs: &LogSink, sources: &[String]) -> Result<TimeSync, String> {
+    let sync = get_reliable_time(logs, sources).await?;
+    if sync.source == "system" {
+        return Err("no time source reachable".to_string());
+    }
+    Ok(sync)
+}
+
+/// A sync round that has to land before the freeze; `remaining` reads the time left to the
+/// first send. The round is cut off at the freeze and a result arriving after it is refused, so
+/// the caller can apply an `Ok` sample right away, without awaiting anything in between.
+pub async fn sync_before_freeze(logs: &LogSink, sources: &[String], remaining: impl Fn() -> i64) -> Result<TimeSync, String> {
+    let budget_ms = remaining() - FREEZE_BEFORE_MS;
+    if budget_ms <= 0 {
+        return Err("clock already frozen for firing".to_string());
+    }
+    let sync = tokio::time::timeout(Duration::from_millis(budget_ms as u64), sync_once(logs, sources))
+        .await
+        .map_err(|_| "sync round cut off at the freeze".to_string())??;
+    if remaining() <= FREEZE_BEFORE_MS {
+        return Err("late sync result discarded - clock already frozen".to_string());
+    }
+    Ok(sync)
+}
+
+/// Queries `sources` in order and returns the lowest-latency answer, or the average of all
+/// answers when they disagree by more than 2s. Falls back to the system clock when all fail.
+async fn get_reliable_time(logs: &LogSink, sources: &[String]) -> Result<TimeSync, String> {
+    let time_sources: Vec<(&str, String)> = sources.iter()
+        .map(|url| url.trim())
+        .filter(|url| !url.is_empty())
+        .map(|url| {
+            let name = reqwest::Url::parse(url).ok()
+                .and_then(|parsed| parsed.host_str().map(str::to_string))
+                .unwrap_or_else(|| url.to_string());
+            (url, name)
+        })
+        .collect();
+    
+    logs.push(LogEvent::info(Category::TimeSync, "🌐 Synchronizing time from reliable sources...".to_string()));
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(6))
+        .connect_timeout(Duration::from_secs(4))
+        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36")
+        .tcp_keepalive(Duration::from_secs(30))
+        .pool_idle_timeout(Duration::from_secs(60))
+        .pool_max_idle_per_host(10)
+        .build()
+        .map_err(|e| format!("HTTP client error: {}", e))?;
+
+    let mut successful_syncs = Vec::new();
+
+    for (url, name) in time_sources {
+        logs.push(LogEvent::info(Category::TimeSync, format!("🔍 Trying {}...", name)));
+        
+        let start_time = Instant::now();
+        
+        match client.get(url).send().await {
+            Ok(response) => {
+                let taken = Instant::now();
+                let network_latency = taken - start_time;
+                
+                if response.status().is_success() {
+                    match response.text().await {
+                        Ok(text) => {
+                            match parse_time_from_json(&text, &name) {
+                                Ok((time, resolution)) => {
+                                    let compensated_time = time + chrono::Duration::from_std(network_latency / 2)
+                                        .unwrap_or(chrono::Duration::zero());
+                                
+                                    let time_sync = TimeSync {
+                                        time: compensated_time,
+                                        latency: network_latency,
+                                        source: name.to_string(),
+                                        uncertainty: network_latency / 2 + resolution,
+                                        taken,
+                                    };
+                                
+                                    successful_syncs.push(time_sync);
+                                
+                                    logs.push(LogEvent::success(Category::TimeSync, format!("✅ {} sync OK - {} (latency: {}ms)", 
+                                                   name, 
+                                                   compensated_time.format("%H:%M:%S.%3f UTC"),
+                                                   network_latency.as_millis())));
+
+                                    if successful_syncs.len() >= 5 {
+                                        break;
+                                    }
+                                }
+                                Err(e) => {
+                                    logs.push(LogEvent::warn(Category::TimeSync, format!("⚠️ {}", e)));
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            logs.push(LogEvent::warn(Category::TimeSync, format!("⚠️ {} response read error: {}", name, e)));
+                        }
+                    }
+                } else {
+                    logs.push(LogEvent::warn(Category::TimeSync, format!("⚠️ {} HTTP error: {}", name, response.status())));
+                }
+            }
+            Err(e) => {
+                logs.push(LogEvent::warn(Category::TimeSync, format!("⚠️ {} connection failed: {}", name, e)));
+            }
+        }
+    }
+
+    if !successful_syncs.is_empty() {
+        successful_syncs.sort_by_key(|sync| sync.latency);
+        
+        let best_sync = &successful_syncs[0];
+        
+        logs.push(LogEvent::success(Category::TimeSync, format!("🏆 Best server selected: {} (latency: {}ms)", 
+                       best_sync.source, best_sync.latency.as_millis())));
+        
+        // Sources answered at different instants; compare them all at the best one's.
+        let projected_ms: Vec<i64> = successful_syncs.iter()
+            .map(|sync| sync.time_at_ms(best_sync.taken))
+            .collect();
+        let time_differences: Vec<i64> = projected_ms.iter()
+            .map(|ms| (ms - best_sync.time.timestamp_millis()).abs())
+            .collect();
+        
+        let max_difference = time_differences.iter().max().unwrap_or(&0);
+        
+        if *max_difference > 2000 { 
+            logs.push(LogEvent::warn(Category::TimeSync, format!("⚠️ Large time difference detected ({}ms), using average instead", max_difference)));
+            
+            let avg_timestamp = projected_ms.iter().sum::<i64>() / projected_ms.len() as i64;
+                
+            let averaged_time = DateTime::from_timestamp_millis(avg_timestamp)
+                .unwrap_or_else(Utc::now);
+                
+            logs.push(LogEvent::info(Category::TimeSync, format!("📊 Time sync completed: {} sources, averaged time: {}", 
+                           successful_syncs.len(), averaged_time.format("%H:%M:%S.%3f UTC"))));
+            
+            return Ok(TimeSync {
+                time: averaged_time,
+                latency: successful_syncs.iter().map(|sync| sync.latency).max().unwrap_or_default(),
+                source: format!("average of {} sources", successful_syncs.len()),
+                uncertainty: Duration::from_millis(*max_difference as u64 / 2)
+                    + successful_syncs.iter().map(|sync| sync.uncertainty).max().unwrap_or_default(),
+                taken: best_sync.taken,
+            });
+        } else {
+            logs.push(LogEvent::info(Category::TimeSync, format!("📊 Time sync completed: {} sources, using best server ({}ms max diff)", 
+                           successful_syncs.len(), max_difference)));
+            
+            return Ok(best_sync.clone());
+        }
+    }
+
+    logs.push(LogEvent::warn(Category::TimeSync, "⚠️ All time APIs failed - using system time (less precise)".to_string()));
+    Ok(TimeSync {
+        time: Utc::now(),
+        latency: Duration::ZERO,
+        source: "system".to_string(),
+        uncertainty: SYSTEM_CLOCK_UNCERTAINTY,
+        taken: Instant::now(),
+    })
+}
+
+/// Returns the parsed time and the error its resolution adds (half a tick).
+fn parse_time_from_json(json_text: &str, source: &str) -> Result<(DateTime<Utc>, Duration), String> {
+    if let Ok(json) = serde_json::from_str::<serde_json::Value>(json_text) {
+        let date_fields = ["dateTime", "datetime", "currentDateTime", "utc_datetime", "time", "current_time"];
+        
+        for field in &date_fields {
+            if let Some(datetime_str) = json[field].as_str() {
+                if let Ok(dt) = DateTime::parse_from_rfc3339(datetime_str) {
+                    let resolution = if datetime_str.contains('.') { Duration::from_millis(1) } else { Duration::from_millis(500) };
+                    return Ok((dt.with_timezone(&Utc), resolution));
+                }
+            }
+        }
+        if let Some(timestamp) = json["unixtime"].as_i64() {
+            if let Some(dt) = DateTime::from_timestamp(timestamp, 0) {
+                return Ok((dt, Duration::from_millis(500)));
+            }
+        }
+        if let Some(timestamp_ms) = json["timestamp"].as_i64() {
+            if let Some(dt) = DateTime::from_timestamp_millis(timestamp_ms) {
+                return Ok((dt, Duration::from_millis(1)));
+            }
+        }
+    }
+    // Sources without a time field are not a measurement; reading the local clock here
+    // would be reported as synced. Failing leaves the estimate on the system clock.
+    Err(format!("{} returned no time field", source))
+}
+
+/// Background refinement of `clock` until `first_send_ms`. Rounds get more frequent as the
+/// release approaches and stop `FREEZE_BEFORE_MS` ahead of it; a sample only replaces the
+/// estimate when its uncertainty beats the current one's aged uncertainty.
+pub async fn refine(
+    first_send_ms: i64,
+    clock: Arc<Mutex<ClockTracker>>,
+    last_sync: Arc<Mutex<Option<TimeSync>>>,
+    sources: Vec<String>,
+    logs: LogSink,
+) {
+    let remaining = || first_send_ms - clock.lock().unwrap().estimate.now_ms();
+    while let Some(pause) = next_sync_in(remaining(), sources.len()) {
+        sleep(pause).await;
+        if !sync_allowed(remaining(), sources.len()) {
+            break;
+        }
+        match sync_before_freeze(&logs, &sources, remaining).await {
+            Ok(sync) => {
+                let mut tracker = clock.lock().unwrap();
+                let previous = tracker.estimate.aged_uncertainty();
+                if tracker.add(&sync) {
+                    logs.push(LogEvent::success(Category::TimeSync, format!("🔄 Clock refined via {}: ±{}ms (was ±{}ms)",
+                                   sync.source, sync.uncertainty.as_millis(), previous.as_millis()))
+                        .field("offset_ms", tracker.estimate.clock_offset_ms())
+                        .field("uncertainty_ms", sync.uncertainty.as_millis()));
+                    *last_sync.lock().unwrap() = Some(sync);
+                } else {
+                    logs.push(LogEvent::info(Category::TimeSync, format!("🔄 Sync sample ±{}ms kept out - current estimate ±{}ms is better",
+                                   sync.uncertainty.as_millis(), previous.as_millis())));
+                }
+                if let Some(ppm) = tracker.drift_ppm() {
+                    logs.push(LogEvent::debug(Category::TimeSync, format!("📐 Clock drift {:+.1} ppm over {} samples", ppm, tracker.sample_count()))
+                        .field("drift_ppm", format!("{:.2}", ppm)));
+                }
+            }
+            Err(e) => logs.push(LogEvent::warn(Category::TimeSync, format!("⚠️ Background sync failed: {}", e))),
+        }
+    }
+    let estimate = clock.lock().unwrap().estimate.clone();
+    logs.push(LogEvent::info(Category::TimeSync, format!("🧊 Clock frozen for firing: {} ±{}ms, drift {:+.1} ppm",
+                   estimate.source, estimate.uncertainty.as_millis(), estimate.drift_ppm)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    /// A tracker holding one sample per `step` over `count` steps, with the server gaining
+    /// `ppm` on the monotonic clock and uniform noise of up to `noise_ms` on each sample.
+    fn tracker(count: usize, step: Duration, ppm: f64, noise_ms: f64, seed: u64) -> ClockTracker {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let start = Instant::now();
+        let mut tracker = ClockTracker::default();
+        for i in 0..count {
+            let elapsed_ms = (step * i as u32).as_secs_f64() * 1000.0;
+            let noise = if noise_ms > 0.0 { rng.gen_range(-noise_ms..=noise_ms) } else { 0.0 };
+            tracker.samples.push_back(SyncSample {
+                at: start + step * i as u32,
+                server_ms: 1_700_000_000_000 + (elapsed_ms * (1.0 + ppm / 1e6) + noise).round() as i64,
+                uncertainty_ms: noise_ms.max(1.0),
+            });
+        }
+        tracker.drift = tracker.fit_drift();
+        tracker
+    }
+
+    #[test]
+    fn noise_alone_is_not_drift() {
+        // Three ±50ms samples over two minutes: the slope's error is hundreds of ppm.
+        let short = tracker(3, Duration::from_secs(60), 0.0, 50.0, 1);
+        assert!(short.drift.is_some_and(|fit| fit.se_ppm > MAX_DRIFT_SE_PPM));
+        assert_eq!(short.drift_ppm(), None);
+        for seed in 0..20 {
+            let long = tracker(60, Duration::from_secs(60), 0.0, 5.0, seed);
+            assert_eq!(long.drift_ppm(), None, "seed {} fitted {:?}", seed, long.drift.map(|fit| fit.ppm));
+        }
+    }
+
+    #[test]
+    fn recovers_a_real_drift() {
+        for seed in 0..5 {
+            let tracker = tracker(60, Duration::from_secs(60), 20.0, 5.0, seed);
+            let ppm = tracker.drift_ppm().expect("20 ppm should be significant");
+            assert!((ppm - 20.0).abs() < 2.0, "seed {} fitted {:.2} ppm", seed, ppm);
+        }
+    }
+
+    #[test]
+    fn implausible_drift_is_rejected() {
+        let tracker = tracker(60, Duration::from_secs(60), 300.0, 5.0, 0);
+        assert!(tracker.drift.is_some_and(|fit| (fit.ppm - 300.0).abs() < 5.0));
+        assert_eq!(tracker.drift_ppm(), None);
+    }
+
+    #[test]
+    fn fit_error_ages_the_uncertainty() {
+        let sync = TimeSync {
+            time: Utc::now(),
+            latency: Duration::from_millis(40),
+            source: "test".to_string(),
+            uncertainty: Duration::from_millis(20),
+            taken: Instant::now() - Duration::from_secs(100),
+        };
+        let mut estimate = ClockEstimate::from_sync(&sync);
+        let allowance_only = estimate.aged_uncertainty();
+        estimate.drift_se_ppm = 10.0;
+        // 100s at 2 x 10 ppm adds 2ms on top of the allowance.
+        let with_fit = estimate.aged_uncertainty();
+        assert!(with_fit >= allowance_only + Duration::from_micros(1_900), "{:?} vs {:?}", with_fit, allowance_only);
+    }
+}
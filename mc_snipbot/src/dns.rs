@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, SocketAddr};
+use std::time::{Duration, Instant};
+use tokio::net::{lookup_host, TcpStream};
+use tokio::time::timeout;
+
+pub const API_HOST: &str = "api.minecraftservices.com";
+const API_PORT: u16 = 443;
+const PROBE_SAMPLES: usize = 3;
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum EdgeMode {
+    #[default]
+    System,
+    Fastest,
+    Spread(usize),
+}
+
+impl EdgeMode {
+    pub fn label(&self) -> String {
+        match self {
+            EdgeMode::System => "System resolver".to_string(),
+            EdgeMode::Fastest => "Pin fastest IP".to_string(),
+            EdgeMode::Spread(n) => format!("Spread across top {}", n),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct EdgeProbe {
+    pub addr: IpAddr,
+    /// Best connect time, or `None` when every attempt failed.
+    pub rtt: Option<Duration>,
+    pub samples: Vec<Duration>,
+}
+
+/// Resolves every address of `host` and measures the TCP connect time to each one.
+/// The result is sorted fastest first, unreachable addresses last.
+pub async fn probe_edges(host: &str) -> Result<Vec<EdgeProbe>, String> {
+    let mut addrs: Vec<IpAddr> = lookup_host((host, API_PORT))
+        .await
+        .map_err(|e| format!("DNS lookup for {} failed: {}", host, e))?
+        .map(|a| a.ip())
+        .collect();
+    addrs.sort();
+    addrs.dedup();
+
+    if addrs.is_empty() {
+        return Err(format!("DNS lookup for {} returned no addresses", host));
+    }
+
+    let mut probes = Vec::new();
+    for addr in addrs {
+        let mut samples = Vec::new();
+        for _ in 0..PROBE_SAMPLES {
+            let start = Instant::now();
+            if let Ok(Ok(_stream)) = timeout(PROBE_TIMEOUT, TcpStream::connect(SocketAddr::new(addr, API_PORT))).await {
+                samples.push(start.elapsed());
+            }
+        }
+        probes.push(EdgeProbe { addr, rtt: samples.iter().min().copied(), samples });
+    }
+
+    probes.sort_by_key(|p| p.rtt.unwrap_or(Duration::MAX));
+    Ok(probes)
+}
+
+/// Picks the IPs the burst should be pinned to, one entry per client.
+/// An empty result means the system resolver should be used.
+pub fn select_edges(probes: &[EdgeProbe], mode: EdgeMode) -> Vec<IpAddr> {
+    let reachable = probes.iter().filter(|p| p.rtt.is_some()).map(|p| p.addr);
+    match mode {
+        EdgeMode::System => Vec::new(),
+        EdgeMode::Fastest => reachable.take(1).collect(),
+        EdgeMode::Spread(n) => reachable.take(n.max(1)).collect(),
+    }
+}
+
+/// Socket address the reqwest resolver override should map `API_HOST` to.
+pub fn pinned_addr(ip: IpAddr) -> SocketAddr {
+    SocketAddr::new(ip, API_PORT)
+}
+
+/// Identifies the network we are on by the local address outbound traffic leaves from.
+/// Nothing is sent: connecting a UDP socket only selects the route.
+pub fn network_id() -> String {
+    std::net::UdpSocket::bind("0.0.0.0:0")
+        .and_then(|socket| {
+            socket.connect("1.1.1.1:53")?;
+            socket.local_addr()
+        })
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_else(|_| "unknown".to_string())
+}
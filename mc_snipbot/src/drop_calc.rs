@@ -0,0 +1,28 @@
+use chrono::{DateTime, Duration, Utc};
+
+/// Days a released name is held before anyone else can claim it.
+pub const DEFAULT_HOLD_DAYS: u32 = 37;
+
+/// When a name frees up, given when the previous owner let it go.
+#[derive(Clone, Copy)]
+pub struct DropEstimate {
+    pub nominal: DateTime<Utc>,
+    pub earliest: DateTime<Utc>,
+    pub latest: DateTime<Utc>,
+}
+
+impl DropEstimate {
+    pub fn window(&self) -> Duration {
+        self.latest - self.earliest
+    }
+}
+
+/// `reference` is the previous owner's change (or drop) time, known to within ±`uncertainty`.
+pub fn estimate(reference: DateTime<Utc>, hold: Duration, uncertainty: Duration) -> DropEstimate {
+    let nominal = reference + hold;
+    DropEstimate {
+        nominal,
+        earliest: nominal - uncertainty,
+        latest: nominal + uncertainty,
+    }
+}
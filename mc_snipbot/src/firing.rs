@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::oneshot;
+
+/// Priority requested for the firing thread under `SCHED_FIFO`.
+#[cfg(target_os = "linux")]
+const FIFO_PRIORITY: i32 = 50;
+/// Nice value tried when real-time scheduling is not permitted.
+#[cfg(target_os = "linux")]
+const FALLBACK_NICE: i32 = -10;
+
+/// How the burst's send instants are timed.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FiringOptions {
+    /// Time sends from a dedicated OS thread instead of tokio timers.
+    pub dedicated: bool,
+    /// Pin the firing thread to this CPU.
+    pub cpu: Option<usize>,
+    /// Ask for real-time scheduling priority.
+    pub realtime: bool,
+}
+
+/// Whether the dedicated thread uses absolute-deadline sleeps on this platform.
+pub fn supported() -> bool {
+    cfg!(target_os = "linux")
+}
+
+/// What the firing thread set up and how late it woke for each deadline.
+pub struct FiringReport {
+    pub setup: Vec<String>,
+    pub wakeups: Vec<Duration>,
+}
+
+impl FiringReport {
+    pub fn summary(&self) -> String {
+        if self.wakeups.is_empty() {
+            return format!("no deadlines fired ({})", self.setup.join(", "));
+        }
+        let mut sorted = self.wakeups.clone();
+        sorted.sort();
+        let micros = |d: Duration| d.as_micros();
+        format!(
+            "wake-up jitter over {} deadlines: median {}µs, p90 {}µs, max {}µs ({})",
+            sorted.len(),
+            micros(sorted[sorted.len() / 2]),
+            micros(sorted[(sorted.len() - 1) * 9 / 10]),
+            micros(sorted[sorted.len() - 1]),
+            self.setup.join(", ")
+        )
+    }
+}
+
+/// Starts a thread that sleeps to each absolute deadline and hands the index of the due send
+/// to the network layer through `fire`. Deadlines must be sorted. Stops early once `stop` is set.
+/// The sender is dropped when the thread exits, which ends the receiver's stream; the report
+/// then arrives on the returned channel, which closes without one if the thread panicked.
+pub fn spawn(
+    deadlines: Vec<Instant>,
+    options: FiringOptions,
+    stop: Arc<AtomicBool>,
+    fire: UnboundedSender<usize>,
+) -> oneshot::Receiver<FiringReport> {
+    let (report_tx, report) = oneshot::channel();
+    std::thread::Builder::new()
+        .name("snipe-firing".to_string())
+        .spawn(move || {
+            let setup = configure_thread(options);
+            let mut wakeups = Vec::with_capacity(deadlines.len());
+            for (index, deadline) in deadlines.into_iter().enumerate() {
+                sleep_until(deadline);
+                wakeups.push(Instant::now().saturating_duration_since(deadline));
+                if stop.load(Ordering::SeqCst) || fire.send(index).is_err() {
+                    break;
+                }
+            }
+            let _ = report_tx.send(FiringReport { setup, wakeups });
+        })
+        .expect("failed to spawn firing thread");
+    report
+}
+
+#[cfg(target_os = "linux")]
+fn configure_thread(options: FiringOptions) -> Vec<String> {
+    let mut setup = Vec::new();
+    if let Some(cpu) = options.cpu {
+        // SAFETY: `set` is a plain bitmask owned by this frame; pid 0 targets the calling thread.
+        let pinned = unsafe {
+            let mut set: libc::cpu_set_t = std::mem::zeroed();
+            libc::CPU_SET(cpu, &mut set);
+            libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) == 0
+        };
+        setup.push(if pinned {
+            format!("pinned to CPU {}", cpu)
+        } else {
+            format!("CPU {} pin failed: {}", cpu, std::io::Error::last_os_error())
+        });
+    }
+    if options.realtime {
+        let param = libc::sched_param { sched_priority: FIFO_PRIORITY };
+        // SAFETY: pid 0 targets the calling thread and `param` outlives the call.
+        if unsafe { libc::sched_setscheduler(0, libc::SCHED_FIFO, &param) } == 0 {
+            setup.push(format!("SCHED_FIFO {}", FIFO_PRIORITY));
+        } else {
+            let fifo_error = std::io::Error::last_os_error();
+            // SAFETY: plain syscalls on the calling thread's id.
+            let reniced = unsafe {
+                let tid = libc::syscall(libc::SYS_gettid) as libc::id_t;
+                libc::setpriority(libc::PRIO_PROCESS, tid, FALLBACK_NICE) == 0
+            };
+            setup.push(if reniced {
+                format!("SCHED_FIFO denied ({}), nice {}", fifo_error, FALLBACK_NICE)
+            } else {
+                format!("priority unchanged ({})", fifo_error)
+            });
+        }
+    }
+    setup.push("clock_nanosleep absolute".to_string());
+    setup
+}
+
+#[cfg(not(target_os = "linux"))]
+fn configure_thread(_options: FiringOptions) -> Vec<String> {
+    vec!["thread::sleep".to_string()]
+}
+
+/// Sleeps on `CLOCK_MONOTONIC` with `TIMER_ABSTIME`, so the deadline does not move when the
+/// sleep is interrupted or the thread is scheduled late.
+#[cfg(target_os = "linux")]
+fn sleep_until(deadline: Instant) {
+    let target = monotonic_now() + deadline.saturating_duration_since(Instant::now());
+    let ts = libc::timespec {
+        tv_sec: target.as_secs() as libc::time_t,
+        tv_nsec: target.subsec_nanos() as libc::c_long,
+    };
+    // SAFETY: `ts` is a valid timespec; a null remainder is allowed with TIMER_ABSTIME.
+    while unsafe { libc::clock_nanosleep(libc::CLOCK_MONOTONIC, libc::TIMER_ABSTIME, &ts, std::ptr::null_mut()) }
+        == libc::EINTR
+    {}
+}
+
+#[cfg(target_os = "linux")]
+fn monotonic_now() -> Duration {
+    let mut ts = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+    // SAFETY: `ts` is a valid out-pointer.
+    unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut ts) };
+    Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn sleep_until(deadline: Instant) {
+    std::thread::sleep(deadline.saturating_duration_since(Instant::now()));
+}
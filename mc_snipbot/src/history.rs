@@ -0,0 +1,348 @@
+use crate::mission::{AttemptRecord, TimingProfile};
+use chrono::{DateTime, Utc};
+use eframe::egui::Color32;
+use rusqlite::{params, Connection};
+use std::path::Path;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS missions (
+    id INTEGER PRIMARY KEY,
+    started_ms INTEGER NOT NULL,
+    target TEXT NOT NULL,
+    account TEXT NOT NULL,
+    network TEXT NOT NULL,
+    release_ms INTEGER NOT NULL,
+    ms_offset INTEGER NOT NULL,
+    burst_count INTEGER NOT NULL,
+    window_ms INTEGER NOT NULL,
+    spacing_ms INTEGER NOT NULL,
+    sync_source TEXT NOT NULL,
+    sync_uncertainty_ms INTEGER,
+    clock_offset_ms INTEGER NOT NULL,
+    drift_ppm REAL,
+    sync_samples INTEGER NOT NULL,
+    outcome TEXT NOT NULL,
+    verdict TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS missions_by_release ON missions (release_ms);
+CREATE TABLE IF NOT EXISTS attempts (
+    mission_id INTEGER NOT NULL REFERENCES missions (id) ON DELETE CASCADE,
+    attempt INTEGER NOT NULL,
+    planned_ms INTEGER NOT NULL,
+    sent_ms INTEGER NOT NULL,
+    responded_ms INTEGER,
+    status INTEGER,
+    error TEXT,
+    server_date_ms INTEGER,
+    body TEXT NOT NULL,
+    PRIMARY KEY (mission_id, attempt)
+);
+";
+
+/// Everything kept about one mission, fired or aborted.
+#[derive(Clone, Default)]
+pub struct MissionRecord {
+    /// Row id; 0 until stored.
+    pub id: i64,
+    pub started_at: DateTime<Utc>,
+    pub target: String,
+    /// Label of the account the token was entered or stored under.
+    pub account: String,
+    pub network: String,
+    pub release_ms: i64,
+    pub timing: TimingProfile,
+    pub spacing_ms: i64,
+    /// Clock estimate the burst was timed with.
+    pub sync_source: String,
+    pub sync_uncertainty_ms: Option<i64>,
+    pub clock_offset_ms: i64,
+    pub drift_ppm: Option<f64>,
+    pub sync_samples: usize,
+    /// `MissionOutcome::kind` of the verdict.
+    pub outcome: String,
+    pub verdict: String,
+    pub attempts: Vec<AttemptRecord>,
+}
+
+impl MissionRecord {
+    pub fn fired(&self) -> bool {
+        !self.attempts.is_empty()
+    }
+
+    pub fn outcome_color(&self) -> Color32 {
+        match self.outcome.as_str() {
+            "claimed" => Color32::GREEN,
+            "unconfirmed" => Color32::from_rgb(255, 165, 0),
+            "aborted" => Color32::GRAY,
+            _ => Color32::RED,
+        }
+    }
+
+    pub fn release(&self) -> DateTime<Utc> {
+        DateTime::from_timestamp_millis(self.release_ms).unwrap_or_default()
+    }
+
+    /// Median round trip of the attempts that got a response.
+    pub fn median_rtt_ms(&self) -> Option<i64> {
+        let mut rtts: Vec<i64> = self.attempts.iter()
+            .filter_map(|a| Some(a.responded?.timestamp_millis() - a.sent.timestamp_millis()))
+            .collect();
+        rtts.sort_unstable();
+        rtts.get(rtts.len() / 2).copied()
+    }
+
+    /// Median of actual minus planned send time on the synced clock; how late the firing path runs.
+    pub fn send_lag_ms(&self) -> Option<i64> {
+        let mut lags: Vec<i64> = self.attempts.iter()
+            .map(|a| a.sent.timestamp_millis() + self.clock_offset_ms - a.planned.timestamp_millis())
+            .collect();
+        lags.sort_unstable();
+        lags.get(lags.len() / 2).copied()
+    }
+
+    /// Estimated server arrival (send + RTT/2 on the synced clock) of each answered attempt,
+    /// relative to release.
+    pub fn arrival_errors_ms(&self) -> Vec<i64> {
+        self.attempts.iter()
+            .filter_map(|a| {
+                let rtt = a.responded?.timestamp_millis() - a.sent.timestamp_millis();
+                Some(a.sent.timestamp_millis() + self.clock_offset_ms + rtt / 2 - self.release_ms)
+            })
+            .collect()
+    }
+}
+
+fn open(path: &Path) -> Result<Connection, String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Cannot create {}: {}", parent.display(), e))?;
+    }
+    let conn = Connection::open(path).map_err(|e| format!("Cannot open {}: {}", path.display(), e))?;
+    conn.execute_batch(SCHEMA)
+        .and_then(|_| conn.pragma_update(None, "foreign_keys", true))
+        .map_err(|e| format!("Cannot prepare {}: {}", path.display(), e))?;
+    Ok(conn)
+}
+
+/// Stores `record` with its attempts and returns the new row id.
+pub fn insert(path: &Path, record: &MissionRecord) -> Result<i64, String> {
+    let mut conn = open(path)?;
+    let write = |conn: &mut Connection| -> rusqlite::Result<i64> {
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT INTO missions (started_ms, target, account, network, release_ms, ms_offset, burst_count,
+                window_ms, spacing_ms, sync_source, sync_uncertainty_ms, clock_offset_ms, drift_ppm,
+                sync_samples, outcome, verdict)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+            params![
+                record.started_at.timestamp_millis(),
+                record.target,
+                record.account,
+                record.network,
+                record.release_ms,
+                record.timing.ms_offset,
+                record.timing.burst_count,
+                record.timing.window_ms,
+                record.spacing_ms,
+                record.sync_source,
+                record.sync_uncertainty_ms,
+                record.clock_offset_ms,
+                record.drift_ppm,
+                record.sync_samples as i64,
+                record.outcome,
+                record.verdict,
+            ],
+        )?;
+        let id = tx.last_insert_rowid();
+        {
+            let mut insert_attempt = tx.prepare(
+                "INSERT INTO attempts (mission_id, attempt, planned_ms, sent_ms, responded_ms, status, error,
+                    server_date_ms, body)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            )?;
+            for attempt in &record.attempts {
+                insert_attempt.execute(params![
+                    id,
+                    attempt.attempt as i64,
+                    attempt.planned.timestamp_millis(),
+                    attempt.sent.timestamp_millis(),
+                    attempt.responded.map(|t| t.timestamp_millis()),
+                    attempt.status,
+                    attempt.error,
+                    attempt.server_date.map(|t| t.timestamp_millis()),
+                    attempt.body,
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(id)
+    };
+    write(&mut conn).map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+}
+
+/// Up to `limit` missions, newest first, optionally only those targeting `target`.
+pub fn load(path: &Path, limit: usize, target: Option<&str>) -> Result<Vec<MissionRecord>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let conn = open(path)?;
+    let read = || -> rusqlite::Result<Vec<MissionRecord>> {
+        let mut missions = conn.prepare(
+            "SELECT id, started_ms, target, account, network, release_ms, ms_offset, burst_count, window_ms,
+                spacing_ms, sync_source, sync_uncertainty_ms, clock_offset_ms, drift_ppm, sync_samples,
+                outcome, verdict
+             FROM missions
+             WHERE ?1 IS NULL OR target = ?1 COLLATE NOCASE
+             ORDER BY started_ms DESC, id DESC
+             LIMIT ?2",
+        )?;
+        let mut records = missions
+            .query_map(params![target, limit as i64], |row| {
+                Ok(MissionRecord {
+                    id: row.get(0)?,
+                    started_at: millis(row.get(1)?),
+                    target: row.get(2)?,
+                    account: row.get(3)?,
+                    network: row.get(4)?,
+                    release_ms: row.get(5)?,
+                    timing: TimingProfile {
+                        ms_offset: row.get(6)?,
+                        burst_count: row.get(7)?,
+                        window_ms: row.get(8)?,
+                    },
+                    spacing_ms: row.get(9)?,
+                    sync_source: row.get(10)?,
+                    sync_uncertainty_ms: row.get(11)?,
+                    clock_offset_ms: row.get(12)?,
+                    drift_ppm: row.get(13)?,
+                    sync_samples: row.get::<_, i64>(14)? as usize,
+                    outcome: row.get(15)?,
+                    verdict: row.get(16)?,
+                    attempts: Vec::new(),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut attempts = conn.prepare(
+            "SELECT attempt, planned_ms, sent_ms, responded_ms, status, error, server_date_ms, body
+             FROM attempts WHERE mission_id = ?1 ORDER BY attempt",
+        )?;
+        for record in &mut records {
+            record.attempts = attempts
+                .query_map([record.id], |row| {
+                    Ok(AttemptRecord {
+                        attempt: row.get::<_, i64>(0)? as usize,
+                        planned: millis(row.get(1)?),
+                        sent: millis(row.get(2)?),
+                        responded: row.get::<_, Option<i64>>(3)?.map(millis),
+                        status: row.get(4)?,
+                        error: row.get(5)?,
+                        server_date: row.get::<_, Option<i64>>(6)?.map(millis),
+                        body: row.get(7)?,
+                    })
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+        }
+        Ok(records)
+    };
+    read().map_err(|e| format!("Cannot read {}: {}", path.display(), e))
+}
+
+/// Row count, for telling an empty database from an unmatched filter.
+pub fn count(path: &Path) -> Result<i64, String> {
+    if !path.exists() {
+        return Ok(0);
+    }
+    let conn = open(path)?;
+    conn.query_row("SELECT COUNT(*) FROM missions", [], |row| row.get(0))
+        .map_err(|e| format!("Cannot read {}: {}", path.display(), e))
+}
+
+fn millis(ms: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(ms).unwrap_or_default()
+}
+
+/// One line of the `history` listing.
+pub fn summary_line(record: &MissionRecord) -> String {
+    let timing = if record.timing.window_ms > 0 {
+        format!("-{}ms ±{}s window", record.timing.ms_offset, record.timing.window_ms / 1000)
+    } else {
+        format!("-{}ms x{}", record.timing.ms_offset, record.timing.burst_count)
+    };
+    let sync = match record.sync_uncertainty_ms {
+        Some(ms) => format!("{} ±{}ms", record.sync_source, ms),
+        None => "system clock".to_string(),
+    };
+    format!(
+        "#{:<4} {}  {:<16} {:<12} release {}  {:<20} {:<24} {} sent, RTT {}  {}",
+        record.id,
+        record.started_at.format("%Y-%m-%d %H:%M"),
+        record.target,
+        record.account,
+        record.release().format("%Y-%m-%d %H:%M:%S%.3f"),
+        timing,
+        sync,
+        record.attempts.len(),
+        record.median_rtt_ms().map_or("?".to_string(), |ms| format!("{}ms", ms)),
+        record.verdict
+    )
+}
+
+pub const CLI_USAGE: &str = "Usage: snipebot history [--limit 20] [--target NAME] [--attempts]
+
+Lists recorded missions, newest first. --attempts adds each mission's requests.";
+
+/// `snipebot history ...`: lists stored missions.
+pub fn run_cli(path: &Path, args: &[String]) -> Result<String, String> {
+    let mut limit = 20usize;
+    let mut target = None;
+    let mut show_attempts = false;
+
+    let mut iter = args.iter();
+    while let Some(flag) = iter.next() {
+        match flag.as_str() {
+            "--help" | "-h" => return Ok(CLI_USAGE.to_string()),
+            "--attempts" => show_attempts = true,
+            "--limit" | "--target" => {
+                let value = iter.next().ok_or_else(|| format!("Missing value for {}\n\n{}", flag, CLI_USAGE))?;
+                if flag == "--limit" {
+                    limit = value.parse().map_err(|_| format!("Invalid value '{}' for {}", value, flag))?;
+                } else {
+                    target = Some(value.clone());
+                }
+            }
+            _ => return Err(format!("Unknown option {}\n\n{}", flag, CLI_USAGE)),
+        }
+    }
+
+    let records = load(path, limit, target.as_deref())?;
+    if records.is_empty() {
+        return Ok(if count(path)? == 0 {
+            format!("No missions recorded yet ({})", path.display())
+        } else {
+            "No missions match".to_string()
+        });
+    }
+    let mut lines = Vec::new();
+    for record in &records {
+        lines.push(summary_line(record));
+        if show_attempts {
+            for attempt in &record.attempts {
+                lines.push(format!("       {}", attempt_line(attempt, record)));
+            }
+        }
+    }
+    Ok(lines.join("\n"))
+}
+
+/// One request of `record`: send time on the synced clock relative to release, lateness, RTT and result.
+pub fn attempt_line(attempt: &AttemptRecord, record: &MissionRecord) -> String {
+    let sent_ms = attempt.sent.timestamp_millis() + record.clock_offset_ms;
+    format!(
+        "#{:<2} sent {:+}ms vs release ({:+}ms vs plan), RTT {}, {}",
+        attempt.attempt,
+        sent_ms - record.release_ms,
+        sent_ms - attempt.planned.timestamp_millis(),
+        attempt.responded.map_or("?".to_string(), |t| format!("{}ms", t.timestamp_millis() - attempt.sent.timestamp_millis())),
+        attempt.result_label()
+    )
+}
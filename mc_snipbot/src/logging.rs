@@ -0,0 +1,238 @@
+use chrono::{DateTime, Utc};
+use eframe::egui::Color32;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Default, Serialize, Deserialize)]
+pub enum Level {
+    Debug,
+    #[default]
+    Info,
+    Success,
+    Warn,
+    Error,
+}
+
+impl Level {
+    pub const ALL: [Level; 5] = [Level::Debug, Level::Info, Level::Success, Level::Warn, Level::Error];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Level::Debug => "DEBUG",
+            Level::Info => "INFO",
+            Level::Success => "OK",
+            Level::Warn => "WARN",
+            Level::Error => "ERROR",
+        }
+    }
+
+    pub fn color(&self) -> Color32 {
+        match self {
+            Level::Debug => Color32::GRAY,
+            Level::Info => Color32::LIGHT_GRAY,
+            Level::Success => Color32::GREEN,
+            Level::Warn => Color32::YELLOW,
+            Level::Error => Color32::from_rgb(255, 80, 80),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Category {
+    General,
+    TimeSync,
+    Countdown,
+    Attempt,
+    Auth,
+    Network,
+    Scheduler,
+    Mission,
+}
+
+impl Category {
+    pub const ALL: [Category; 8] = [
+        Category::General,
+        Category::TimeSync,
+        Category::Countdown,
+        Category::Attempt,
+        Category::Auth,
+        Category::Network,
+        Category::Scheduler,
+        Category::Mission,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Category::General => "General",
+            Category::TimeSync => "TimeSync",
+            Category::Countdown => "Countdown",
+            Category::Attempt => "Attempt",
+            Category::Auth => "Auth",
+            Category::Network => "Network",
+            Category::Scheduler => "Scheduler",
+            Category::Mission => "Mission",
+        }
+    }
+}
+
+/// One entry of the activity log.
+#[derive(Clone)]
+pub struct LogEvent {
+    pub timestamp: DateTime<Utc>,
+    pub level: Level,
+    pub category: Category,
+    pub message: String,
+    pub fields: Vec<(&'static str, String)>,
+}
+
+impl LogEvent {
+    pub fn new(level: Level, category: Category, message: impl Into<String>) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            level,
+            category,
+            message: message.into(),
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn debug(category: Category, message: impl Into<String>) -> Self {
+        Self::new(Level::Debug, category, message)
+    }
+
+    pub fn info(category: Category, message: impl Into<String>) -> Self {
+        Self::new(Level::Info, category, message)
+    }
+
+    pub fn success(category: Category, message: impl Into<String>) -> Self {
+        Self::new(Level::Success, category, message)
+    }
+
+    pub fn warn(category: Category, message: impl Into<String>) -> Self {
+        Self::new(Level::Warn, category, message)
+    }
+
+    pub fn error(category: Category, message: impl Into<String>) -> Self {
+        Self::new(Level::Error, category, message)
+    }
+
+    /// Attaches a structured key/value pair.
+    pub fn field(mut self, key: &'static str, value: impl ToString) -> Self {
+        self.fields.push((key, value.to_string()));
+        self
+    }
+
+    /// `k=v` rendering of the fields, empty when there are none.
+    pub fn fields_text(&self) -> String {
+        self.fields.iter().map(|(key, value)| format!("{}={}", key, value)).collect::<Vec<_>>().join(" ")
+    }
+
+    /// Case-insensitive search over the message, category and fields; `needle` must be lowercase.
+    pub fn matches(&self, needle: &str) -> bool {
+        needle.is_empty()
+            || self.message.to_lowercase().contains(needle)
+            || self.category.label().to_lowercase().contains(needle)
+            || self.fields_text().to_lowercase().contains(needle)
+    }
+}
+
+impl LogEvent {
+    /// One line of the plain-text log file.
+    pub fn to_text(&self) -> String {
+        let mut line = format!(
+            "{} {:<5} [{}] {}",
+            self.timestamp.format("%Y-%m-%dT%H:%M:%S%.3fZ"),
+            self.level.label(),
+            self.category.label(),
+            self.message
+        );
+        if !self.fields.is_empty() {
+            line.push_str(" | ");
+            line.push_str(&self.fields_text());
+        }
+        line
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let fields: serde_json::Map<String, serde_json::Value> = self
+            .fields
+            .iter()
+            .map(|(key, value)| (key.to_string(), serde_json::Value::String(value.clone())))
+            .collect();
+        serde_json::json!({
+            "timestamp": self.timestamp.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            "level": self.level.label(),
+            "category": self.category.label(),
+            "message": self.message,
+            "fields": fields,
+        })
+    }
+}
+
+/// Events the pipeline holds before writers start dropping.
+const PIPELINE_CAPACITY: usize = 4096;
+
+/// Write end of the log pipeline. `push` never blocks: when the recorder falls behind and the
+/// buffer is full the event is dropped and counted, so logging cannot delay the burst.
+#[derive(Clone)]
+pub struct LogSink {
+    sender: SyncSender<LogEvent>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl LogSink {
+    pub fn push(&self, event: LogEvent) {
+        if self.sender.try_send(event).is_err() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+impl Default for LogSink {
+    /// A sink with no reader; everything pushed is dropped.
+    fn default() -> Self {
+        pipeline().0
+    }
+}
+
+/// Read end of the log pipeline, drained by `mission_log::Recorder`.
+pub struct LogDrain {
+    // Only the recorder reads, so this lock is never contended; it just makes the drain `Sync`.
+    receiver: Mutex<Receiver<LogEvent>>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl LogDrain {
+    /// Everything pushed since the last call.
+    pub fn drain(&self) -> Vec<LogEvent> {
+        self.receiver.lock().unwrap().try_iter().collect()
+    }
+
+    /// Events dropped because the pipeline was full.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for LogDrain {
+    fn default() -> Self {
+        pipeline().1
+    }
+}
+
+pub fn pipeline() -> (LogSink, LogDrain) {
+    let (sender, receiver) = sync_channel(PIPELINE_CAPACITY);
+    let dropped = Arc::new(AtomicU64::new(0));
+    (
+        LogSink {
+            sender,
+            dropped: dropped.clone(),
+        },
+        LogDrain {
+            receiver: Mutex::new(receiver),
+            dropped,
+        },
+    )
+}
@@ -1,30 +1,79 @@
+mod burst;
+mod clock_sync;
+mod dns;
+mod firing;
+mod history;
+mod logging;
+mod drop_calc;
+mod mission;
+mod mission_log;
+mod mojang;
+mod paths;
+mod postmortem;
+mod rate_limit;
+mod release_time;
+mod scheduler;
+mod services;
+mod settings;
+mod simulator;
+mod stats;
+mod timeline;
+mod tuning;
+mod vault;
+mod window;
+
 use chrono::{DateTime, Utc};
+use clock_sync::{ClockTracker, TimeSync};
+use dns::{EdgeMode, EdgeProbe};
+use firing::FiringOptions;
+use history::MissionRecord;
+use logging::{Category, Level, LogEvent, LogSink};
+use egui_plot::{Bar, BarChart, Plot, Points, VLine};
+use std::ops::RangeInclusive;
 use eframe::{egui, App, Frame, CreationContext};
-use reqwest::Client;
-use serde_json;
+use mission_log::Recorder;
+use mission::{Countdown, MissionContext, MissionOutcome, MissionParams, TimingProfile};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::{Duration, UNIX_EPOCH};
-use tokio::runtime::Runtime;
-use tokio::time::sleep;
-use egui::{Button, Vec2, ViewportBuilder, TextEdit, Slider};
+use std::time::Duration;
+use egui::{Button, ComboBox, DragValue, Vec2, ViewportBuilder, TextEdit, Slider};
+use rate_limit::RateLimiter;
+use scheduler::{JobState, Scheduler};
+use services::{Command, Event, Services};
+use settings::{Endpoints, Settings};
+use simulator::SimResult;
+use vault::{AccountTokens, Credentials, SecretToken};
+use zeroize::{Zeroize, Zeroizing};
 
-const TIMESTAMP_RELEASE: i64 = 1752474234;
 
-#[derive(Default, Clone)]
-struct LogLine {
-    msg: String,
-}
 
-#[derive(Clone)]
-struct TimeSync {
-    time: DateTime<Utc>,
-    latency: Duration,
-    source: String,
+/// Widest drop window, in seconds either side of the nominal release.
+const MAX_DROP_WINDOW_SECS: u32 = 3600;
+/// Ranges the setting widgets allow; persisted settings are clamped to them when loaded.
+const MS_OFFSET_RANGE: RangeInclusive<i64> = 0..=1000;
+const BURST_RANGE: RangeInclusive<u8> = 1..=16;
+const HOLD_DAYS_RANGE: RangeInclusive<u32> = 0..=365;
+const SIM_HIT_WINDOW_RANGE: RangeInclusive<f64> = 1.0..=1000.0;
+const SIM_TRIALS_RANGE: RangeInclusive<usize> = 1_000..=1_000_000;
+const MAX_FIRING_CPU: usize = 255;
+/// Initial window size, restored by "Reset to defaults".
+const WINDOW_SIZE: [f32; 2] = [650.0, 850.0];
+/// Remaining time below which the big clock takes over, when enabled.
+const BIG_CLOCK_FROM_MS: i64 = 60_000;
+/// Missions the History tab loads.
+const HISTORY_VIEW_LIMIT: usize = 500;
+
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+enum Tab {
+    #[default]
+    Mission,
+    History,
+    Stats,
 }
 
 #[derive(Default)]
 struct SnipeApp {
-    logs: Arc<Mutex<Vec<LogLine>>>,
+    logs: LogSink,
     status: String,
     username: String,
     access_token: String,
@@ -33,415 +82,1228 @@ struct SnipeApp {
     custom_release_time: String,
     current_utc_time: DateTime<Utc>,
     burst_count: u8, 
+    edge_mode: EdgeMode,
+    edges: Arc<Mutex<Vec<EdgeProbe>>>,
+    limiter: Arc<RateLimiter>,
+    outcome: Arc<Mutex<Option<MissionOutcome>>>,
+    account_name: String,
+    /// Jobs are armed by the services' job keeper; the GUI only edits and renders them.
+    scheduler: Arc<Mutex<Scheduler>>,
+    mission_running: Arc<AtomicBool>,
+    calc_reference: String,
+    calc_hold_days: u32,
+    calc_uncertainty_secs: u32,
+    drop_window_secs: u32,
+    last_sync: Arc<Mutex<Option<TimeSync>>>,
+    sim_hit_window_ms: f64,
+    sim_trials: usize,
+    sim_rtts: String,
+    sim_results: Vec<SimResult>,
+    /// A simulation is running in the background.
+    sim_busy: bool,
+    network: String,
+    clock: Arc<Mutex<ClockTracker>>,
+    firing: FiringOptions,
+    firing_cpu: usize,
+    services: Option<Services>,
+    log_min_level: Level,
+    log_category: Option<Category>,
+    recorder: Arc<Mutex<Recorder>>,
+    log_search: String,
+    log_follow: bool,
+    /// Selected rows as (anchor, end) event ids; shift-click moves the end.
+    log_selection: Option<(u64, u64)>,
+    tab: Tab,
+    /// Stored missions, newest first.
+    history: Vec<MissionRecord>,
+    history_filter: String,
+    history_selected: Option<i64>,
+    countdown: Arc<Mutex<Option<Countdown>>>,
+    /// Switch to the big clock for the final minute of a countdown.
+    big_clock_auto: bool,
+    big_clock: bool,
+    /// Set when the big clock was closed during the current countdown, so it stays closed.
+    big_clock_dismissed: bool,
+    endpoints: Arc<Mutex<Endpoints>>,
+    /// Editor buffer for `endpoints.time_sources`, one URL per line.
+    time_sources_text: String,
+    /// Typed token and unlocked vault. Locking drops the vault, which wipes its tokens.
+    credentials: Arc<Mutex<Credentials>>,
+    vault_passphrase: String,
+    vault_confirm: String,
+    vault_refresh_token: String,
+    /// A passphrase is being checked in the background.
+    vault_busy: bool,
+    /// Show the unlock prompt; set at startup when a vault exists.
+    vault_prompt: bool,
 }
 
 impl SnipeApp {
-    fn new() -> Self {
-        Self {
-            logs: Arc::new(Mutex::new(Vec::new())),
+    fn new(storage: Option<&dyn eframe::Storage>) -> Self {
+        let (logs, log_drain) = logging::pipeline();
+        let settings: Settings = storage
+            .and_then(|storage| eframe::get_value(storage, settings::STORAGE_KEY))
+            .unwrap_or_default();
+        let mut app = Self {
+            logs,
             status: "Ready".to_string(),
-            username: String::new(),
             access_token: String::new(),
             is_running: false,
-            ms_offset: 20,
             custom_release_time: String::new(),
             current_utc_time: Utc::now(),
-            burst_count: 5, 
+            edges: Arc::new(Mutex::new(Vec::new())),
+            limiter: Arc::new(RateLimiter::default()),
+            outcome: Arc::new(Mutex::new(None)),
+            scheduler: Arc::new(Mutex::new(Scheduler::default())),
+            mission_running: Arc::new(AtomicBool::new(false)),
+            calc_reference: String::new(),
+            calc_uncertainty_secs: 0,
+            last_sync: Arc::new(Mutex::new(None)),
+            sim_rtts: String::new(),
+            sim_results: Vec::new(),
+            network: dns::network_id(),
+            clock: Arc::new(Mutex::new(ClockTracker::default())),
+            services: None,
+            log_category: None,
+            recorder: Arc::new(Mutex::new(Recorder::new(log_drain))),
+            log_search: String::new(),
+            log_follow: true,
+            log_selection: None,
+            tab: Tab::Mission,
+            history: Vec::new(),
+            history_filter: String::new(),
+            history_selected: None,
+            countdown: Arc::new(Mutex::new(None)),
+            big_clock: false,
+            big_clock_dismissed: false,
+            endpoints: Arc::new(Mutex::new(Endpoints::default())),
+            ..Default::default()
+        };
+        app.apply_settings(settings);
+        match Scheduler::load(paths::jobs_file(), Utc::now().timestamp_millis()) {
+            Ok(scheduler) => {
+                *app.scheduler.lock().unwrap() = scheduler;
+                app.save_jobs();
+            }
+            Err(e) => app.log(LogEvent::warn(Category::Scheduler, format!("⚠️ Scheduled jobs not loaded: {}", e))),
         }
+        match Services::start(app.mission_context()) {
+            Ok(services) => app.services = Some(services),
+            Err(e) => app.log(LogEvent::error(Category::General, format!("❌ {}", e))),
+        }
+        app.load_history();
+        app.vault_prompt = paths::vault_file().exists();
+        app
     }
 
-    fn log(&self, text: impl Into<String>) {
-        let mut logs = self.logs.lock().unwrap();
-        logs.push(LogLine { msg: text.into() });
+    fn log(&self, event: LogEvent) {
+        self.logs.push(event);
     }
 
-    async fn get_reliable_time(&self) -> Result<DateTime<Utc>, String> {
-        let time_sources = vec![
-            ("https://api.frankfurter.app/latest", "frankfurter.app"),
-        ];
-        
-        self.log("🌐 Synchronizing time from reliable sources...".to_string());
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(6))
-            .connect_timeout(Duration::from_secs(4))
-            .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36")
-            .tcp_keepalive(Duration::from_secs(30))
-            .pool_idle_timeout(Duration::from_secs(60))
-            .pool_max_idle_per_host(10)
-            .build()
-            .map_err(|e| format!("HTTP client error: {}", e))?;
-
-        let mut successful_syncs = Vec::new();
-        let mut time_samples = Vec::new();
-
-        for (url, name) in time_sources {
-            self.log(format!("🔍 Trying {}...", name));
-            
-            let start_time = std::time::Instant::now();
-            
-            match client.get(url).send().await {
-                Ok(response) => {
-                    let network_latency = start_time.elapsed();
-                    
-                    if response.status().is_success() {
-                        match response.text().await {
-                            Ok(text) => {
-                                if let Ok(time) = self.parse_time_from_json(&text, name) {
-                                    let compensated_time = time + chrono::Duration::from_std(network_latency / 2)
-                                        .unwrap_or(chrono::Duration::zero());
-                                    
-                                    let time_sync = TimeSync {
-                                        time: compensated_time,
-                                        latency: network_latency,
-                                        source: name.to_string(),
-                                    };
-                                    
-                                    successful_syncs.push(time_sync.clone());
-                                    time_samples.push(compensated_time);
-                                    
-                                    self.log(format!("✅ {} sync OK - {} (latency: {}ms)", 
-                                                   name, 
-                                                   compensated_time.format("%H:%M:%S.%3f UTC"),
-                                                   network_latency.as_millis()));
-
-                                    if successful_syncs.len() >= 5 {
-                                        break;
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                self.log(format!("⚠️ {} response read error: {}", name, e));
-                            }
+    fn timing_profile(&self) -> TimingProfile {
+        TimingProfile {
+            ms_offset: self.ms_offset,
+            burst_count: self.burst_count,
+            window_ms: self.drop_window_secs * 1000,
+        }
+    }
+
+    fn parse_release_time(&self) -> Result<DateTime<Utc>, String> {
+        release_time::parse_release(&self.custom_release_time, Utc::now())
+    }
+
+    fn mission_context(&self) -> MissionContext {
+        MissionContext {
+            logs: self.logs.clone(),
+            edges: self.edges.clone(),
+            limiter: self.limiter.clone(),
+            outcome: self.outcome.clone(),
+            last_sync: self.last_sync.clone(),
+            clock: self.clock.clone(),
+            countdown: self.countdown.clone(),
+            endpoints: self.endpoints.clone(),
+            scheduler: self.scheduler.clone(),
+            credentials: self.credentials.clone(),
+            mission_running: self.mission_running.clone(),
+            recorder: self.recorder.clone(),
+        }
+    }
+
+    /// The persisted part of the GUI state.
+    fn settings(&self) -> Settings {
+        Settings {
+            username: self.username.clone(),
+            account_name: self.account_name.clone(),
+            ms_offset: self.ms_offset,
+            burst_count: self.burst_count,
+            drop_window_secs: self.drop_window_secs,
+            edge_mode: self.edge_mode,
+            firing: self.firing,
+            endpoints: self.endpoints.lock().unwrap().clone(),
+            calc_hold_days: self.calc_hold_days,
+            sim_hit_window_ms: self.sim_hit_window_ms,
+            sim_trials: self.sim_trials,
+            log_min_level: self.log_min_level,
+            big_clock_auto: self.big_clock_auto,
+        }
+    }
+
+    fn apply_settings(&mut self, settings: Settings) {
+        self.username = settings.username;
+        self.account_name = settings.account_name;
+        self.ms_offset = settings.ms_offset.clamp(*MS_OFFSET_RANGE.start(), *MS_OFFSET_RANGE.end());
+        self.burst_count = settings.burst_count.clamp(*BURST_RANGE.start(), *BURST_RANGE.end());
+        self.drop_window_secs = settings.drop_window_secs.min(MAX_DROP_WINDOW_SECS);
+        self.edge_mode = settings.edge_mode;
+        self.firing = FiringOptions {
+            cpu: settings.firing.cpu.map(|cpu| cpu.min(MAX_FIRING_CPU)),
+            ..settings.firing
+        };
+        self.firing_cpu = self.firing.cpu.unwrap_or(0);
+        self.time_sources_text = settings.endpoints.time_sources.join("\n");
+        *self.endpoints.lock().unwrap() = settings.endpoints;
+        self.calc_hold_days = settings.calc_hold_days.clamp(*HOLD_DAYS_RANGE.start(), *HOLD_DAYS_RANGE.end());
+        self.sim_hit_window_ms = if settings.sim_hit_window_ms.is_finite() {
+            settings.sim_hit_window_ms.clamp(*SIM_HIT_WINDOW_RANGE.start(), *SIM_HIT_WINDOW_RANGE.end())
+        } else {
+            simulator::DEFAULT_HIT_WINDOW_MS
+        };
+        self.sim_trials = settings.sim_trials.clamp(*SIM_TRIALS_RANGE.start(), *SIM_TRIALS_RANGE.end());
+        self.log_min_level = settings.log_min_level;
+        self.big_clock_auto = settings.big_clock_auto;
+    }
+
+    /// Restores every persisted setting and the window layout to the defaults. Tokens are kept.
+    fn reset_settings(&mut self, ctx: &egui::Context) {
+        self.apply_settings(Settings::default());
+        ctx.memory_mut(|memory| *memory = Default::default());
+        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(WINDOW_SIZE.into()));
+        self.log(LogEvent::info(Category::General, "↩️ Settings reset to defaults"));
+    }
+
+    fn show_endpoints(&mut self, ui: &mut egui::Ui) {
+        let mut endpoints = self.endpoints.lock().unwrap().clone();
+        ui.add_enabled_ui(!self.is_running, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("API base URL:");
+                ui.add(TextEdit::singleline(&mut endpoints.api_base).desired_width(300.0));
+            });
+            if endpoints.api_host().is_none() {
+                ui.colored_label(egui::Color32::RED, format!("   ✖ Not a URL - edges are probed on {}", dns::API_HOST));
+            }
+            ui.label("Time sources (one URL per line):");
+            if ui.add(TextEdit::multiline(&mut self.time_sources_text).desired_rows(3).desired_width(400.0)).changed() {
+                endpoints.time_sources = self.time_sources_text.lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(str::to_string)
+                    .collect();
+            }
+            if endpoints.time_sources.is_empty() {
+                ui.colored_label(egui::Color32::YELLOW, "   ⚠ No time source - syncs fall back to the system clock");
+            }
+        });
+        let mut shared = self.endpoints.lock().unwrap();
+        if *shared != endpoints {
+            *shared = endpoints;
+        }
+    }
+
+    /// Hands a command to the background services. Returns `false` when they are not running.
+    fn send(&self, command: Command) -> bool {
+        match &self.services {
+            Some(services) => {
+                services.send(command);
+                true
+            }
+            None => {
+                self.log(LogEvent::error(Category::General, "❌ Background services are not running"));
+                false
+            }
+        }
+    }
+
+    fn probe_edges(&self) {
+        self.send(Command::ProbeEdges);
+    }
+
+    fn sync_clock(&self) {
+        self.send(Command::SyncClock);
+    }
+
+    fn run_preflight(&self) {
+        let access_token = self.token_for(self.account_name.trim());
+        let Some(access_token) = access_token.filter(|_| !self.username.trim().is_empty()) else {
+            self.log(LogEvent::error(Category::Auth, "❌ Enter a username and access token to run the pre-flight check!"));
+            return;
+        };
+        self.send(Command::Preflight {
+            access_token,
+            username: self.username.trim().to_string(),
+        });
+    }
+
+    fn handle_events(&mut self) {
+        let events = match self.services.as_mut() {
+            Some(services) => services.poll(),
+            None => return,
+        };
+        for event in events {
+            match event {
+                Event::MissionStarted => self.mission_started(),
+                Event::MissionFinished => self.finish_mission(),
+                Event::VaultOpened(result) => {
+                    self.vault_busy = false;
+                    match result {
+                        Ok(vault) => {
+                            self.log(LogEvent::success(Category::Auth, format!("🔓 Token vault unlocked ({} accounts)", vault.accounts().count())));
+                            self.credentials.lock().unwrap().vault = Some(vault);
+                            self.vault_prompt = false;
                         }
-                    } else {
-                        self.log(format!("⚠️ {} HTTP error: {}", name, response.status()));
+                        Err(e) => self.log(LogEvent::error(Category::Auth, format!("❌ Token vault: {}", e))),
+                    }
+                }
+                Event::Simulated(result) => {
+                    self.sim_busy = false;
+                    match result {
+                        Ok(result) => {
+                            self.log(LogEvent::info(Category::General, format!("🎲 {}", result.summary())));
+                            self.sim_results.push(result);
+                        }
+                        Err(e) => self.log(LogEvent::error(Category::General, format!("❌ Simulation failed: {}", e))),
                     }
                 }
-                Err(e) => {
-                    self.log(format!("⚠️ {} connection failed: {}", name, e));
+                Event::TaskFailed { task, error } => {
+                    self.log(LogEvent::error(Category::General, format!("❌ Background {} crashed: {}", task, error)));
+                    if task == "vault" {
+                        self.vault_busy = false;
+                    }
+                    if task == "simulation" {
+                        self.sim_busy = false;
+                    }
+                    if task == "mission" {
+                        *self.outcome.lock().unwrap() = Some(MissionOutcome::Aborted(format!("crashed: {}", error)));
+                        self.finish_mission();
+                    }
                 }
             }
         }
+    }
 
-        if !successful_syncs.is_empty() {
-            successful_syncs.sort_by(|a, b| a.latency.cmp(&b.latency));
-            
-            let best_sync = &successful_syncs[0];
-            
-            self.log(format!("🏆 Best server selected: {} (latency: {}ms)", 
-                           best_sync.source, best_sync.latency.as_millis()));
-            
-            let time_differences: Vec<i64> = successful_syncs.iter()
-                .map(|sync| (sync.time.timestamp_millis() - best_sync.time.timestamp_millis()).abs())
-                .collect();
-            
-            let max_difference = time_differences.iter().max().unwrap_or(&0);
-            
-            if *max_difference > 2000 { 
-                self.log(format!("⚠️ Large time difference detected ({}ms), using average instead", max_difference));
-                
-                let avg_timestamp = time_samples.iter()
-                    .map(|t| t.timestamp_millis())
-                    .sum::<i64>() / time_samples.len() as i64;
-                    
-                let averaged_time = DateTime::from_timestamp_millis(avg_timestamp)
-                    .unwrap_or_else(|| Utc::now());
-                    
-                self.log(format!("📊 Time sync completed: {} sources, averaged time: {}", 
-                               successful_syncs.len(), averaged_time.format("%H:%M:%S.%3f UTC")));
-                
-                return Ok(averaged_time);
-            } else {
-                self.log(format!("📊 Time sync completed: {} sources, using best server ({}ms max diff)", 
-                               successful_syncs.len(), max_difference));
-                
-                return Ok(best_sync.time);
+    fn export_log(&self, events: Vec<LogEvent>) {
+        let stem = format!("snipebot_{}", Utc::now().format("%Y%m%dT%H%M%S"));
+        match mission_log::export(&paths::exports_dir(), &stem, &events) {
+            Ok(path) => self.log(LogEvent::success(Category::General, format!("💾 Exported {} events to {}", events.len(), path.display()))),
+            Err(e) => self.log(LogEvent::error(Category::General, format!("❌ Log export failed: {}", e))),
+        }
+    }
+
+    fn finish_mission(&mut self) {
+        self.is_running = false;
+        *self.countdown.lock().unwrap() = None;
+        self.load_history();
+    }
+
+    fn start_snipe(&mut self) {
+        if self.username.trim().is_empty() {
+            self.log(LogEvent::error(Category::General, "❌ Please enter a username to snipe!"));
+            return;
+        }
+        
+        let Some(access_token) = self.token_for(self.account_name.trim()) else {
+            self.log(LogEvent::error(Category::Auth, "❌ Please enter your Minecraft access token or unlock the vault!"));
+            return;
+        };
+
+        if self.custom_release_time.trim().is_empty() {
+            self.log(LogEvent::error(Category::General, "❌ Please enter a custom release time!"));
+            return;
+        }
+
+        let release = match self.parse_release_time() {
+            Ok(release) => release,
+            Err(e) => {
+                self.log(LogEvent::error(Category::General, format!("❌ Invalid release time: {}", e)));
+                return;
             }
+        };
+
+        self.launch(MissionParams {
+            username: self.username.clone(),
+            account: self.account_name.trim().to_string(),
+            access_token,
+            release_ms: release.timestamp_millis(),
+            timing: self.timing_profile(),
+            edge_mode: self.edge_mode,
+            firing: self.firing,
+            job: None,
+        });
+    }
+
+    fn token_for(&self, account: &str) -> Option<SecretToken> {
+        self.credentials.lock().unwrap().token_for(account)
+    }
+
+    /// Mirrors the token field into the shared credentials, where the job keeper finds it.
+    fn publish_typed_token(&self) {
+        let typed = (!self.access_token.trim().is_empty()).then(|| (self.account_name.trim(), self.access_token.trim()));
+        let mut credentials = self.credentials.lock().unwrap();
+        if credentials.typed.as_ref().map(|(account, token)| (account.as_str(), token.as_str())) != typed {
+            credentials.typed = typed.map(|(account, token)| (account.to_string(), Arc::new(Zeroizing::new(token.to_string()))));
         }
+    }
 
-        self.log("⚠️ All time APIs failed - using system time (less precise)".to_string());
-        Ok(Utc::now())
+    fn open_vault(&mut self, create: bool) {
+        if create && self.vault_passphrase != self.vault_confirm {
+            self.log(LogEvent::error(Category::Auth, "❌ The passphrases do not match"));
+            return;
+        }
+        let passphrase = Zeroizing::new(std::mem::take(&mut self.vault_passphrase));
+        self.vault_confirm.zeroize();
+        if self.send(Command::OpenVault { passphrase, create }) {
+            self.vault_busy = true;
+        }
     }
 
-    fn parse_time_from_json(&self, json_text: &str, source: &str) -> Result<DateTime<Utc>, String> {
-        if let Ok(json) = serde_json::from_str::<serde_json::Value>(json_text) {
-            let date_fields = ["dateTime", "datetime", "currentDateTime", "utc_datetime", "time", "current_time"];
-            
-            for field in &date_fields {
-                if let Some(datetime_str) = json[field].as_str() {
-                    if let Ok(dt) = DateTime::parse_from_rfc3339(datetime_str) {
-                        return Ok(dt.with_timezone(&Utc));
+    /// Drops the vault, which wipes its key and tokens from memory.
+    fn lock_vault(&mut self) {
+        if self.credentials.lock().unwrap().vault.take().is_some() {
+            self.vault_refresh_token.zeroize();
+            self.log(LogEvent::info(Category::Auth, "🔒 Token vault locked"));
+        }
+    }
+
+    /// Stores the typed token (and optional refresh token) under the current account,
+    /// then clears the input so the vault holds the only copy.
+    fn store_token(&mut self) {
+        let account = self.account_name.trim().to_string();
+        let refresh = self.vault_refresh_token.trim();
+        let tokens = AccountTokens {
+            bearer: self.access_token.trim().to_string(),
+            refresh: (!refresh.is_empty()).then(|| refresh.to_string()),
+        };
+        let stored = match self.credentials.lock().unwrap().vault.as_mut() {
+            Some(vault) => vault.set(&account, tokens),
+            None => return,
+        };
+        match stored {
+            Ok(()) => {
+                self.access_token.zeroize();
+                self.vault_refresh_token.zeroize();
+                self.log(LogEvent::success(Category::Auth, format!("🔐 Token for '{}' saved to the vault", account)));
+            }
+            Err(e) => self.log(LogEvent::error(Category::Auth, format!("❌ Token vault: {}", e))),
+        }
+    }
+
+    fn show_vault(&mut self, ui: &mut egui::Ui) {
+        let credentials = self.credentials.clone();
+        let credentials = credentials.lock().unwrap();
+        let Some(vault) = credentials.vault.as_ref() else {
+            drop(credentials);
+            let exists = paths::vault_file().exists();
+            ui.label(if exists {
+                "🔒 Locked - unlock to use the stored tokens."
+            } else {
+                "No vault yet. Tokens are encrypted with a key derived from your passphrase (Argon2id, XChaCha20-Poly1305)."
+            });
+            ui.add_enabled_ui(!self.vault_busy, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Passphrase:");
+                    let field = ui.add(TextEdit::singleline(&mut self.vault_passphrase).password(true).desired_width(200.0));
+                    if exists && field.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter)) {
+                        self.open_vault(false);
                     }
+                });
+                if !exists {
+                    ui.horizontal(|ui| {
+                        ui.label("Confirm:");
+                        ui.add(TextEdit::singleline(&mut self.vault_confirm).password(true).desired_width(200.0));
+                    });
                 }
-            }
-            if let Some(timestamp) = json["unixtime"].as_i64() {
-                if let Some(dt) = DateTime::from_timestamp(timestamp, 0) {
-                    return Ok(dt);
+                let label = if exists { "🔓 Unlock" } else { "🔐 Create vault" };
+                if ui.button(label).clicked() {
+                    self.open_vault(!exists);
                 }
+            });
+            if self.vault_busy {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label("Deriving key...");
+                });
             }
-            if let Some(timestamp_ms) = json["timestamp"].as_i64() {
-                if let Some(dt) = DateTime::from_timestamp_millis(timestamp_ms) {
-                    return Ok(dt);
-                }
+            return;
+        };
+
+        let mut use_account = None;
+        let mut remove = None;
+        let mut lock = false;
+        let mut store = false;
+        ui.horizontal(|ui| {
+            ui.colored_label(egui::Color32::LIGHT_GREEN, format!("🔓 Unlocked - {} accounts", vault.accounts().count()));
+            lock = ui.button("🔒 Lock").clicked();
+        });
+        egui::Grid::new("vault_accounts").striped(true).num_columns(4).show(ui, |ui| {
+            for (account, tokens) in vault.accounts() {
+                ui.label(account);
+                let tail: String = tokens.bearer.chars().rev().take(4).collect::<Vec<_>>().into_iter().rev().collect();
+                ui.monospace(format!("••••{}", tail));
+                ui.label(if tokens.refresh.is_some() { "refresh token" } else { "" });
+                ui.horizontal(|ui| {
+                    if ui.small_button("Use").on_hover_text("Select this account").clicked() {
+                        use_account = Some(account.clone());
+                    }
+                    if ui.small_button("🗑").on_hover_text("Remove from the vault").clicked() {
+                        remove = Some(account.clone());
+                    }
+                });
+                ui.end_row();
             }
+        });
+        ui.horizontal(|ui| {
+            ui.add(TextEdit::singleline(&mut self.vault_refresh_token)
+                .hint_text("Refresh token (optional)")
+                .password(true)
+                .desired_width(180.0));
+            let can_store = !self.access_token.trim().is_empty() && !self.account_name.trim().is_empty();
+            if ui.add_enabled(can_store, Button::new(format!("💾 Save token as '{}'", self.account_name.trim())))
+                .on_disabled_hover_text("Enter an account label and a token first")
+                .clicked()
+            {
+                store = true;
+            }
+        });
+        drop(credentials);
+
+        if store {
+            self.store_token();
         }
-        if source.contains("github.com") || source.contains("httpbin.org") || 
-           source.contains("coindesk.com") || source.contains("exchangerate-api.com") ||
-           source.contains("frankfurter.app") || source.contains("fixer.io") ||
-           source.contains("jsonplaceholder.typicode.com") || source.contains("httpstat.us") ||
-           source.contains("quotable.io") || source.contains("adviceslip.com") ||
-           source.contains("chucknorris.io") || source.contains("official-joke-api") ||
-           source.contains("openweathermap.org") || source.contains("sunrise-sunset.org") {
-            return Ok(Utc::now());
+        if let Some(account) = use_account {
+            self.account_name = account;
+            self.access_token.zeroize();
+        }
+        if let Some(account) = remove {
+            let removed = self.credentials.lock().unwrap().vault.as_mut().map(|vault| vault.remove(&account));
+            if let Some(Err(e)) = removed {
+                self.log(LogEvent::error(Category::Auth, format!("❌ Token vault: {}", e)));
+            }
+        }
+        if lock {
+            self.lock_vault();
         }
-        
-        Err(format!("Cannot parse time from {}", source))
     }
 
-    fn get_release_timestamp(&self) -> i64 {
-        if !self.custom_release_time.trim().is_empty() {
-            if let Ok(parsed_time) = DateTime::parse_from_rfc3339(&format!("{}Z", self.custom_release_time)) {
-                return parsed_time.timestamp();
-            }
+    /// Startup prompt for an existing vault; "Later" leaves it locked.
+    fn show_vault_prompt(&mut self, ctx: &egui::Context) {
+        let mut open = self.vault_prompt;
+        egui::Window::new("🔐 Unlock token vault")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+            .open(&mut open)
+            .show(ctx, |ui| self.show_vault(ui));
+        self.vault_prompt = open && self.credentials.lock().unwrap().vault.is_none();
+    }
+
+    fn save_jobs(&self) {
+        if let Err(e) = self.scheduler.lock().unwrap().save() {
+            self.log(LogEvent::warn(Category::Scheduler, format!("⚠️ Could not save scheduled jobs: {}", e)));
         }
-        TIMESTAMP_RELEASE
     }
 
-    fn start_snipe(&mut self) {
+    fn schedule_job(&mut self) {
         if self.username.trim().is_empty() {
-            self.log("❌ Please enter a username to snipe!");
+            self.log(LogEvent::error(Category::General, "❌ Please enter a username to snipe!"));
             return;
         }
-        
-        if self.access_token.trim().is_empty() {
-            self.log("❌ Please enter your Minecraft access token!");
+
+        if self.account_name.trim().is_empty() {
+            self.log(LogEvent::error(Category::Scheduler, "❌ Please enter an account label for the job!"));
             return;
         }
 
-        if self.custom_release_time.trim().is_empty() {
-            self.log("❌ Please enter a custom release time!");
+        let release = match self.parse_release_time() {
+            Ok(release) => release,
+            Err(e) => {
+                self.log(LogEvent::error(Category::General, format!("❌ Invalid release time: {}", e)));
+                return;
+            }
+        };
+        if release <= self.current_utc_time {
+            self.log(LogEvent::error(Category::General, "❌ Release time is in the past!"));
             return;
         }
 
-        if DateTime::parse_from_rfc3339(&format!("{}Z", self.custom_release_time)).is_err() {
-            self.log("❌ Invalid time format! Use YYYY-MM-DDTHH:MM:SS");
+        let id = self.scheduler.lock().unwrap().add(&self.username, release.timestamp_millis(), &self.account_name,
+                                                    self.timing_profile(), self.edge_mode, self.firing);
+        self.save_jobs();
+        self.log(LogEvent::info(Category::Scheduler, format!("🗓️ Job #{} scheduled: '{}' at {} for account '{}'",
+                         id, self.username.trim(), release_time::describe(release), self.account_name.trim())));
+    }
+
+    fn show_drop_calculator(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("📅 Previous owner changed at:");
+            ui.add(TextEdit::singleline(&mut self.calc_reference)
+                .hint_text(release_time::FORMAT_HINT)
+                .desired_width(300.0));
+        });
+        ui.horizontal(|ui| {
+            ui.label("⏳ Hold period:");
+            ui.add(DragValue::new(&mut self.calc_hold_days).clamp_range(HOLD_DAYS_RANGE).suffix(" days"));
+            ui.label("± Reference uncertainty:");
+            ui.add(DragValue::new(&mut self.calc_uncertainty_secs).clamp_range(0..=86_400).suffix(" s"));
+        });
+
+        if self.calc_reference.trim().is_empty() {
             return;
         }
+        let reference = match release_time::parse_release(&self.calc_reference, Utc::now()) {
+            Ok(reference) => reference,
+            Err(e) => {
+                ui.colored_label(egui::Color32::RED, format!("   ✖ {}", e));
+                return;
+            }
+        };
 
-        self.status = "In progress...".into();
-        self.is_running = true;
-        let logs = self.logs.clone();
-        let username = self.username.clone();
-        let access_token = self.access_token.clone();
-        let ms_offset = self.ms_offset;
-        let release_timestamp = self.get_release_timestamp();
-        
-        std::thread::spawn(move || {
-            let rt = Runtime::new().unwrap();
-            rt.block_on(async move {
-                let snipe_client = Client::builder()
-                    .timeout(Duration::from_secs(10))
-                    .connect_timeout(Duration::from_secs(5))
-                    .user_agent("SnipeBot/1.0")
-                    .build()
-                    .unwrap();
-                let release_time = UNIX_EPOCH + Duration::from_secs(release_timestamp as u64);
-                let release_dt: DateTime<Utc> = release_time.into();
-                let first_request_time_ms = release_dt.timestamp_millis() - ms_offset;
-                logs.lock().unwrap().push(LogLine {
-                    msg: format!("🎯 Target username: {}", username),
+        let estimate = drop_calc::estimate(
+            reference,
+            chrono::Duration::days(self.calc_hold_days as i64),
+            chrono::Duration::seconds(self.calc_uncertainty_secs as i64),
+        );
+        ui.monospace(format!("Earliest availability: {}", release_time::describe(estimate.earliest)));
+        ui.monospace(format!("Nominal:               {}", release_time::describe(estimate.nominal)));
+        ui.monospace(format!("Latest:                {}", release_time::describe(estimate.latest)));
+        ui.monospace(format!("Uncertainty window:    {}s", estimate.window().num_seconds()));
+
+        ui.horizontal(|ui| {
+            // An uncertain reference becomes a drop window centred on the nominal instant.
+            let use_button = ui.button("📥 Use as release time").clicked();
+            let schedule_button = ui.button("🗓️ Schedule job").clicked();
+            if use_button || schedule_button {
+                self.custom_release_time = estimate.nominal.format("%Y-%m-%dT%H:%M:%S%.3f").to_string();
+                self.drop_window_secs = self.calc_uncertainty_secs.min(MAX_DROP_WINDOW_SECS);
+                if self.calc_uncertainty_secs > MAX_DROP_WINDOW_SECS {
+                    self.log(LogEvent::warn(Category::General, format!("⚠️ Reference uncertainty ±{}s exceeds the widest drop window - using ±{}s",
+                                     self.calc_uncertainty_secs, MAX_DROP_WINDOW_SECS)));
+                }
+            }
+            if schedule_button {
+                self.schedule_job();
+            }
+        });
+    }
+
+    fn load_history(&mut self) {
+        match history::load(&paths::history_db(), HISTORY_VIEW_LIMIT, None) {
+            Ok(history) => self.history = history,
+            Err(e) => self.log(LogEvent::warn(Category::General, format!("⚠️ Mission history not loaded: {}", e))),
+        }
+    }
+
+    fn show_history(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("🔎 Target:");
+            ui.add(TextEdit::singleline(&mut self.history_filter).hint_text("All names").desired_width(150.0));
+            if ui.button("🔄 Refresh").clicked() {
+                self.load_history();
+            }
+            ui.weak(format!("{} missions in {}", self.history.len(), paths::history_db().display()));
+        });
+        if self.history.is_empty() {
+            ui.label("No missions recorded yet.");
+            return;
+        }
+
+        let filter = self.history_filter.trim().to_lowercase();
+        let mut toggled = None;
+        egui::ScrollArea::vertical().id_source("history_missions").max_height(400.0).show(ui, |ui| {
+            egui::Grid::new("history").striped(true).num_columns(8).show(ui, |ui| {
+                ui.strong("Started");
+                ui.strong("Target");
+                ui.strong("Account");
+                ui.strong("Release (UTC)");
+                ui.strong("Timing");
+                ui.strong("Clock");
+                ui.strong("RTT");
+                ui.strong("Result");
+                ui.end_row();
+                for record in self.history.iter().filter(|record| record.target.to_lowercase().contains(&filter)) {
+                    ui.monospace(record.started_at.format("%Y-%m-%d %H:%M").to_string());
+                    if ui.selectable_label(self.history_selected == Some(record.id), &record.target)
+                        .on_hover_text("Show attempts")
+                        .clicked()
+                    {
+                        toggled = Some(record.id);
+                    }
+                    ui.label(&record.account);
+                    ui.monospace(record.release().format("%Y-%m-%d %H:%M:%S%.3f").to_string());
+                    if record.timing.window_ms > 0 {
+                        ui.label(format!("-{}ms, ±{}s window", record.timing.ms_offset, record.timing.window_ms / 1000));
+                    } else {
+                        ui.label(format!("-{}ms, x{}", record.timing.ms_offset, record.timing.burst_count));
+                    }
+                    ui.label(match record.sync_uncertainty_ms {
+                        Some(ms) => format!("{} ±{}ms", record.sync_source, ms),
+                        None => "system".to_string(),
+                    });
+                    ui.monospace(record.median_rtt_ms().map_or("?".to_string(), |ms| format!("{}ms", ms)));
+                    ui.colored_label(record.outcome_color(), &record.verdict);
+                    ui.end_row();
+                }
+            });
+        });
+        if let Some(id) = toggled {
+            self.history_selected = if self.history_selected == Some(id) { None } else { Some(id) };
+        }
+
+        if let Some(record) = self.history.iter().find(|record| Some(record.id) == self.history_selected) {
+            ui.separator();
+            ui.label(format!("📚 Mission #{} - {} on {}, clock offset {:+}ms, drift {}, {} sync samples",
+                             record.id, record.target, record.network, record.clock_offset_ms,
+                             record.drift_ppm.map_or("not fitted".to_string(), |ppm| format!("{:+.1} ppm", ppm)),
+                             record.sync_samples));
+            if record.attempts.is_empty() {
+                ui.label("No requests were sent.");
+            } else {
+                timeline::show(ui, record);
+            }
+            for attempt in &record.attempts {
+                let line = history::attempt_line(attempt, record);
+                if attempt.is_success() {
+                    ui.colored_label(egui::Color32::GREEN, egui::RichText::new(line).monospace());
+                } else {
+                    ui.monospace(line);
+                }
+            }
+        }
+    }
+
+    fn show_stats(&mut self, ui: &mut egui::Ui) {
+        let counts = stats::outcomes(&self.history);
+        ui.horizontal(|ui| {
+            ui.label(format!("🎯 {} fired, {} aborted:", counts.fired(), counts.aborted));
+            ui.colored_label(egui::Color32::GREEN, format!("{} claimed", counts.claimed));
+            ui.colored_label(egui::Color32::from_rgb(255, 165, 0), format!("{} unconfirmed", counts.unconfirmed));
+            ui.colored_label(egui::Color32::RED, format!("{} not claimed", counts.not_claimed));
+            if let Some(rate) = counts.success_rate() {
+                ui.strong(format!("| success rate {:.0}%", rate * 100.0));
+            }
+            if ui.button("🔄 Refresh").clicked() {
+                self.load_history();
+            }
+        });
+        let fired: Vec<&MissionRecord> = self.history.iter().filter(|record| record.fired()).collect();
+        if fired.is_empty() {
+            ui.label("No fired missions recorded yet.");
+            return;
+        }
+
+        egui::ScrollArea::vertical().id_source("stats").show(ui, |ui| {
+            ui.add_space(5.0);
+            ui.strong("🎯 Estimated arrival vs release");
+            let errors: Vec<i64> = fired.iter().flat_map(|record| record.arrival_errors_ms()).collect();
+            if errors.is_empty() {
+                ui.label("No request got a response.");
+            } else {
+                let (min, max) = (errors.iter().min().copied().unwrap_or(0), errors.iter().max().copied().unwrap_or(0));
+                let width = stats::bin_width(max - min);
+                let bins = stats::histogram(&errors, width);
+                let bars: Vec<Bar> = bins.iter()
+                    .map(|&(start, count)| Bar::new((start as f64) + width as f64 / 2.0, count as f64)
+                        .width(width as f64)
+                        .fill(if start + width <= 0 { egui::Color32::from_rgb(200, 170, 60) } else { egui::Color32::from_rgb(90, 160, 230) })
+                        .name(format!("{}..{}ms: {} requests", start, start + width, count)))
+                    .collect();
+                Plot::new("arrival_errors")
+                    .height(160.0)
+                    .x_axis_label("ms after release (send + RTT/2)")
+                    .y_axis_label("requests")
+                    .include_x(0.0)
+                    .include_y(0.0)
+                    .allow_scroll(false)
+                    .label_formatter(timeline::hover_label)
+                    .show(ui, |plot_ui| {
+                        plot_ui.bar_chart(BarChart::new(bars).element_formatter(Box::new(|bar, _| bar.name.clone())));
+                        plot_ui.vline(VLine::new(0.0).color(egui::Color32::GREEN).name("release"));
+                    });
+                ui.weak(format!("{} answered requests in {}ms bins; early arrivals in amber", errors.len(), width));
+            }
+
+            ui.add_space(10.0);
+            ui.strong("🕐 Median RTT by hour of day (UTC)");
+            let by_hour = stats::rtt_by_hour(&self.history);
+            let bars: Vec<Bar> = by_hour.iter()
+                .map(|&(hour, rtt, samples)| Bar::new(hour as f64 + 0.5, rtt as f64)
+                    .width(1.0)
+                    .fill(egui::Color32::from_rgb(90, 160, 230))
+                    .name(format!("{:02}:00-{:02}:59 UTC: median {}ms over {} requests", hour, hour, rtt, samples)))
+                .collect();
+            Plot::new("rtt_by_hour")
+                .height(160.0)
+                .x_axis_label("hour (UTC)")
+                .y_axis_label("RTT ms")
+                .include_x(0.0)
+                .include_x(24.0)
+                .include_y(0.0)
+                .allow_scroll(false)
+                .show(ui, |plot_ui| {
+                    plot_ui.bar_chart(BarChart::new(bars).element_formatter(Box::new(|bar, _| bar.name.clone())));
                 });
-                
-                logs.lock().unwrap().push(LogLine {
-                    msg: format!("🎯 Target release time: {}", release_dt.format("%Y-%m-%d %H:%M:%S UTC")),
+
+            ui.add_space(10.0);
+            ui.strong("📐 Outcome by offset and burst size");
+            Plot::new("offset_burst")
+                .height(160.0)
+                .x_axis_label("ms_offset")
+                .y_axis_label("requests sent")
+                .include_y(0.0)
+                .allow_scroll(false)
+                .label_formatter(timeline::hover_label)
+                .show(ui, |plot_ui| {
+                    for record in &fired {
+                        plot_ui.points(Points::new([record.timing.ms_offset as f64, record.attempts.len() as f64])
+                            .color(record.outcome_color())
+                            .radius(4.0)
+                            .name(format!("{} on {}: -{}ms, {} sent - {}",
+                                          record.target, record.release().format("%Y-%m-%d"),
+                                          record.timing.ms_offset, record.attempts.len(), record.verdict)));
+                    }
                 });
-                logs.lock().unwrap().push(LogLine {
-                    msg: format!("🚀 First request will be sent at: {} ({}ms before release)", 
-                               DateTime::from_timestamp_millis(first_request_time_ms).unwrap().format("%H:%M:%S.%3f UTC"),
-                               ms_offset),
+            ui.horizontal(|ui| {
+                ui.colored_label(egui::Color32::GREEN, "● claimed");
+                ui.colored_label(egui::Color32::from_rgb(255, 165, 0), "● unconfirmed");
+                ui.colored_label(egui::Color32::RED, "● not claimed");
+            });
+        });
+    }
+
+    /// 95% clock error a mission armed now would plan with: the estimate's aged uncertainty.
+    fn clock_uncertainty_ms(&self) -> i64 {
+        self.clock.lock().unwrap().estimate.aged_uncertainty().as_millis() as i64
+    }
+
+    fn show_tuning(&mut self, ui: &mut egui::Ui) {
+        ui.label(format!("🌐 Network: {}", self.network));
+        let uncertainty_ms = self.clock_uncertainty_ms();
+        let max_burst = self.limiter.budget().requests.min(16);
+        match tuning::recommend(&self.history, &self.network, uncertainty_ms, max_burst) {
+            Some(recommendation) => {
+                ui.horizontal(|ui| {
+                    ui.label("💡 Recommended:");
+                    ui.colored_label(egui::Color32::LIGHT_GREEN, recommendation.summary());
                 });
-                
-                let mut cached_time: Option<DateTime<Utc>> = None;
-                let mut last_sync = std::time::Instant::now();
-                let mut last_log_time = std::time::Instant::now();
-                let sync_interval = Duration::from_secs(45); 
-                
-                loop {
-                    let current_time = {
-                        let need_sync = cached_time.is_none() || last_sync.elapsed() > sync_interval;                      
-                        if need_sync {
-                            let temp_app = SnipeApp { 
-                                logs: logs.clone(), 
-                                status: String::new(),
-                                username: String::new(),
-                                access_token: String::new(),
-                                is_running: false,
-                                ms_offset: 0,
-                                custom_release_time: String::new(),
-                                current_utc_time: Utc::now(),
-                                burst_count: 0, 
-                            };                            
-                            match temp_app.get_reliable_time().await {
-                                Ok(synced_time) => {
-                                    cached_time = Some(synced_time);
-                                    last_sync = std::time::Instant::now();
-                                    logs.lock().unwrap().push(LogLine {
-                                        msg: format!("🔄 Time synchronized to optimal server: {}", synced_time.format("%H:%M:%S.%3f UTC")),
-                                    });
-                                    synced_time
-                                }
-                                Err(e) => {
-                                    logs.lock().unwrap().push(LogLine {
-                                        msg: format!("⚠️ Sync failed: {}", e),
-                                    });                                    
-                                    if let Some(cached) = cached_time {
-                                        let elapsed = last_sync.elapsed();
-                                        let calculated_time = cached + chrono::Duration::from_std(elapsed).unwrap_or(chrono::Duration::zero());
-                                        logs.lock().unwrap().push(LogLine {
-                                            msg: format!("🕐 Using cached server time + {}ms offset", elapsed.as_millis()),
-                                        });
-                                        calculated_time
-                                    } else {
-                                        let fallback_time = Utc::now();
-                                        logs.lock().unwrap().push(LogLine {
-                                            msg: "⚠️ No cached time available, using system time".to_string(),
-                                        });
-                                        fallback_time
-                                    }
-                                }
-                            }
-                        } else {
-                            let elapsed = last_sync.elapsed();
-                            cached_time.unwrap() + chrono::Duration::from_std(elapsed).unwrap_or(chrono::Duration::zero())
-                        }
-                    };
+                if ui.add_enabled(!self.is_running, Button::new("📥 Apply recommendation")).clicked() {
+                    self.ms_offset = recommendation.ms_offset.clamp(*MS_OFFSET_RANGE.start(), *MS_OFFSET_RANGE.end());
+                    self.burst_count = recommendation.burst_count as u8;
+                    self.log(LogEvent::info(Category::General, format!("📈 Applied tuned timing: {}", recommendation.summary())));
+                }
+            }
+            None => {
+                ui.label("No fired missions recorded on this network yet.");
+                return;
+            }
+        }
 
-                    let current_time_ms = current_time.timestamp_millis();
-                    let time_diff_ms = first_request_time_ms - current_time_ms;
-                    
-                    let log_interval = if time_diff_ms < 5000 {
-                        Duration::from_millis(100)  
-                    } else if time_diff_ms < 30000 {
-                        Duration::from_millis(1000)
-                    } else if time_diff_ms < 120000 {
-                        Duration::from_millis(3000) 
-                    } else {
-                        Duration::from_millis(5000)
-                    };
-                    
-                    let should_log = time_diff_ms <= 0 || last_log_time.elapsed() >= log_interval;
-                    if should_log {
-                        last_log_time = std::time::Instant::now();
-                        let time_remaining = if time_diff_ms > 0 {
-                            if time_diff_ms >= 60000 {
-                                format!("{}min {}s", time_diff_ms / 60000, (time_diff_ms % 60000) / 1000)
-                            } else if time_diff_ms >= 1000 {
-                                format!("{}.{}s", time_diff_ms / 1000, (time_diff_ms % 1000) / 100)
-                            } else {
-                                format!("{}ms", time_diff_ms)
-                            }
-                        } else {
-                            "FIRING!".to_string()
+        let recent: Vec<&MissionRecord> = tuning::recent(&self.history, &self.network).collect();
+        let fmt_ms = |value: Option<i64>| value.map_or("?".to_string(), |ms| format!("{:+}ms", ms));
+        egui::Grid::new("timing_history").striped(true).num_columns(7).show(ui, |ui| {
+            ui.strong("Date");
+            ui.strong("Offset");
+            ui.strong("Burst");
+            ui.strong("RTT");
+            ui.strong("Send lag");
+            ui.strong("First arrival");
+            ui.strong("Result");
+            ui.end_row();
+            for record in &recent {
+                let earliest_arrival_ms = record.arrival_errors_ms().into_iter().min();
+                ui.monospace(record.started_at.format("%Y-%m-%d %H:%M").to_string());
+                ui.monospace(format!("{}ms", record.timing.ms_offset));
+                ui.monospace(record.attempts.len().to_string());
+                ui.monospace(record.median_rtt_ms().map_or("?".to_string(), |ms| format!("{}ms", ms)));
+                ui.monospace(fmt_ms(record.send_lag_ms()));
+                let arrival = fmt_ms(earliest_arrival_ms);
+                if earliest_arrival_ms.is_some_and(|ms| ms < 0) {
+                    ui.colored_label(egui::Color32::YELLOW, egui::RichText::new(arrival).monospace());
+                } else {
+                    ui.monospace(arrival);
+                }
+                ui.colored_label(record.outcome_color(), &record.verdict);
+                ui.end_row();
+            }
+        });
+    }
+
+    /// Opens or closes the big clock: automatically for the final minute, closed with Escape,
+    /// and always closed once the countdown ends.
+    fn update_big_clock(&mut self, ctx: &egui::Context, countdown: Option<&Countdown>) {
+        let was_open = self.big_clock;
+        match countdown {
+            None => {
+                self.big_clock = false;
+                self.big_clock_dismissed = false;
+            }
+            Some(countdown) => {
+                let remaining_ms = countdown.first_send_ms - self.clock.lock().unwrap().estimate.now_ms();
+                if self.big_clock_auto && !self.big_clock_dismissed && remaining_ms <= BIG_CLOCK_FROM_MS {
+                    self.big_clock = true;
+                }
+                if self.big_clock && ctx.input(|input| input.key_pressed(egui::Key::Escape)) {
+                    self.big_clock = false;
+                    self.big_clock_dismissed = true;
+                }
+            }
+        }
+        if self.big_clock != was_open {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(self.big_clock));
+        }
+    }
+
+    fn show_countdown(&mut self, ui: &mut egui::Ui, countdown: &Countdown) {
+        let estimate = self.clock.lock().unwrap().estimate.clone();
+        let now_ms = estimate.now_ms();
+        let remaining_ms = countdown.first_send_ms - now_ms;
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(format!("🎯 {} - release {}", countdown.username, synced_time_text(countdown.release_ms)));
+                if ui.button("🔳 Big clock").clicked() {
+                    self.big_clock = true;
+                    ui.ctx().send_viewport_cmd(egui::ViewportCommand::Fullscreen(true));
+                }
+                ui.checkbox(&mut self.big_clock_auto, "Big clock for the final minute");
+            });
+            ui.label(egui::RichText::new(format_countdown(remaining_ms)).monospace().size(36.0).color(countdown_color(remaining_ms)));
+            ui.horizontal(|ui| {
+                ui.label("🕐 Synced time:");
+                ui.monospace(synced_time_text(now_ms));
+                ui.label(format!("| offset {:+}ms ±{}ms via {}",
+                                 estimate.clock_offset_ms(), estimate.aged_uncertainty().as_millis(), estimate.source));
+            });
+            ui.add(egui::ProgressBar::new(countdown.progress(now_ms)).text("until first request"));
+        });
+    }
+
+    fn show_big_clock(&mut self, ctx: &egui::Context, countdown: &Countdown) {
+        let estimate = self.clock.lock().unwrap().estimate.clone();
+        let now_ms = estimate.now_ms();
+        let remaining_ms = countdown.first_send_ms - now_ms;
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space(ui.available_height() * 0.15);
+                ui.label(egui::RichText::new(format!("🎯 {}", countdown.username)).size(32.0));
+                ui.label(egui::RichText::new(format_countdown(remaining_ms)).monospace().size(120.0).color(countdown_color(remaining_ms)));
+                ui.label(egui::RichText::new(synced_time_text(now_ms)).monospace().size(48.0));
+                ui.label(egui::RichText::new(format!("offset {:+}ms ±{}ms via {}",
+                                                     estimate.clock_offset_ms(), estimate.aged_uncertainty().as_millis(), estimate.source))
+                    .size(20.0));
+                ui.add_space(20.0);
+                ui.add(egui::ProgressBar::new(countdown.progress(now_ms)).desired_width(ui.available_width() * 0.6));
+                ui.add_space(20.0);
+                if ui.button("Exit big clock (Esc)").clicked() {
+                    self.big_clock = false;
+                    self.big_clock_dismissed = true;
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(false));
+                }
+            });
+        });
+    }
+
+    fn show_clock(&mut self, ui: &mut egui::Ui) {
+        let tracker = self.clock.lock().unwrap();
+        let estimate = &tracker.estimate;
+        if !estimate.is_synced() {
+            ui.label(format!("Not synced - using the system clock (±{}ms assumed)", estimate.uncertainty.as_millis()));
+            return;
+        }
+        egui::Grid::new("clock_status").num_columns(2).show(ui, |ui| {
+            ui.label("Source:");
+            ui.monospace(format!("{} ({}s ago)", estimate.source, estimate.age().as_secs()));
+            ui.end_row();
+            ui.label("Offset vs system:");
+            ui.monospace(format!("{:+}ms", estimate.clock_offset_ms()));
+            ui.end_row();
+            ui.label("Uncertainty:");
+            ui.monospace(format!("±{}ms (±{}ms at sync)", estimate.aged_uncertainty().as_millis(), estimate.uncertainty.as_millis()));
+            ui.end_row();
+            ui.label("Drift:");
+            match tracker.drift {
+                Some(fit) if fit.is_significant() => ui.monospace(format!("{:+.1} ±{:.1} ppm ({} samples over {}min)",
+                                                 fit.ppm, fit.se_ppm, tracker.sample_count(), tracker.sample_span().as_secs() / 60)),
+                Some(fit) => ui.monospace(format!("not applied: {:+.1} ±{:.1} ppm is within noise ({} samples over {}min)",
+                                                 fit.ppm, fit.se_ppm, tracker.sample_count(), tracker.sample_span().as_secs() / 60)),
+                None => ui.monospace(format!("not fitted yet ({} samples over {}s)",
+                                            tracker.sample_count(), tracker.sample_span().as_secs())),
+            };
+            ui.end_row();
+            ui.label("Synced time:");
+            let now = DateTime::from_timestamp_millis(estimate.now_ms()).unwrap_or(self.current_utc_time);
+            ui.monospace(now.format("%H:%M:%S%.3f UTC").to_string());
+            ui.end_row();
+        });
+    }
+
+    fn show_simulator(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("🎯 Hit window:");
+            ui.add(DragValue::new(&mut self.sim_hit_window_ms).clamp_range(SIM_HIT_WINDOW_RANGE).suffix(" ms after release"));
+            ui.label("Trials:");
+            ui.add(DragValue::new(&mut self.sim_trials).clamp_range(SIM_TRIALS_RANGE).speed(1000));
+        });
+        ui.horizontal(|ui| {
+            ui.label("📶 RTT samples:");
+            ui.add(TextEdit::singleline(&mut self.sim_rtts)
+                .hint_text("40, 45, 52 (ms)")
+                .desired_width(220.0));
+            if ui.button("📡 Use probed RTTs").clicked() {
+                let edges = self.edges.lock().unwrap();
+                let chosen = dns::select_edges(&edges, self.edge_mode);
+                let samples: Vec<String> = edges.iter()
+                    .filter(|probe| chosen.contains(&probe.addr))
+                    .flat_map(|probe| probe.samples.iter())
+                    .map(|rtt| format!("{:.1}", rtt.as_secs_f64() * 1000.0))
+                    .collect();
+                if samples.is_empty() {
+                    self.log(LogEvent::warn(Category::Network, "⚠️ No probe samples yet - press 📡 Probe first".to_string()));
+                } else {
+                    self.sim_rtts = samples.join(", ");
+                }
+            }
+        });
+
+        ui.horizontal(|ui| {
+            if ui.add_enabled(!self.sim_busy, Button::new("🎲 Simulate current settings")).clicked() {
+                let uncertainty_ms = self.clock_uncertainty_ms();
+                match simulator::parse_rtt_list(&self.sim_rtts) {
+                    Ok(rtt_samples_ms) => {
+                        let config = simulator::SimConfig {
+                            ms_offset: self.ms_offset,
+                            offsets_ms: burst::plan(self.burst_count as usize, uncertainty_ms).offsets_ms,
+                            uncertainty_95_ms: uncertainty_ms as f64,
+                            rtt_samples_ms,
+                            hit_window_ms: self.sim_hit_window_ms,
+                            trials: self.sim_trials,
                         };
+                        self.sim_busy = self.send(Command::Simulate { config, seed: Utc::now().timestamp_millis() as u64 });
+                    }
+                    Err(e) => self.log(LogEvent::error(Category::General, format!("❌ Simulation failed: {}", e))),
+                }
+            }
+            if self.sim_busy {
+                ui.spinner();
+            }
+            if !self.sim_results.is_empty() && ui.button("🗑️ Clear").clicked() {
+                self.sim_results.clear();
+            }
+        });
 
-                        logs.lock().unwrap().push(LogLine {
-                            msg: format!("⏱️ Now: {} | Time to snipe: {}", 
-                                       current_time.format("%H:%M:%S.%3f"), time_remaining),
-                        });
+        if self.sim_results.is_empty() {
+            return;
+        }
+        egui::Grid::new("sim_results").striped(true).num_columns(6).show(ui, |ui| {
+            ui.strong("Offset");
+            ui.strong("Burst");
+            ui.strong("Spacing");
+            ui.strong("Clock ±");
+            ui.strong("Early");
+            ui.strong("P(hit)");
+            ui.end_row();
+            for result in &self.sim_results {
+                ui.monospace(format!("{}ms", result.config.ms_offset));
+                ui.monospace(result.config.offsets_ms.len().to_string());
+                ui.monospace(format!("{}ms", result.spacing_ms()));
+                ui.monospace(format!("{:.0}ms", result.config.uncertainty_95_ms));
+                ui.monospace(format!("{:.1}%", result.early_fraction * 100.0));
+                ui.monospace(format!("{:.1}%", result.hit_probability * 100.0));
+                ui.end_row();
+            }
+        });
+    }
+
+    fn show_jobs(&mut self, ui: &mut egui::Ui) {
+        let now = self.current_utc_time.timestamp_millis();
+        let mut cancel = None;
+        let mut remove = None;
+
+        egui::Grid::new("jobs").striped(true).num_columns(6).show(ui, |ui| {
+            ui.strong("Name");
+            ui.strong("Release (UTC)");
+            ui.strong("Account");
+            ui.strong("Timing");
+            ui.strong("State");
+            ui.strong("");
+            ui.end_row();
+
+            for job in &self.scheduler.lock().unwrap().jobs {
+                ui.label(&job.username);
+                ui.monospace(DateTime::from_timestamp_millis(job.release_ms)
+                    .map(|dt| dt.format("%Y-%m-%d %H:%M:%S%.3f").to_string())
+                    .unwrap_or_default());
+                ui.label(&job.account);
+                if job.timing.window_ms > 0 {
+                    ui.label(format!("-{}ms, ±{}s window", job.timing.ms_offset, job.timing.window_ms / 1000));
+                } else {
+                    ui.label(format!("-{}ms, x{}", job.timing.ms_offset, job.timing.burst_count));
+                }
+
+                let (color, text) = match job.state {
+                    JobState::Upcoming if job.arm_at() <= now && self.token_for(&job.account).is_none() => {
+                        (egui::Color32::YELLOW, "Upcoming (needs token)".to_string())
                     }
-                    
-                    if current_time_ms >= first_request_time_ms {
-                        logs.lock().unwrap().push(LogLine {
-                            msg: format!("🚀 LAUNCHING SNIPE ATTACK NOW! ({}ms before release)", ms_offset),
-                        });
-                        
-                        let precise_attack_time = DateTime::from_timestamp_millis(first_request_time_ms)
-                            .unwrap_or(current_time);
-                        
-                        logs.lock().unwrap().push(LogLine {
-                            msg: format!("📡 Attack time based on: {} | Precise time: {}", 
-                                       if cached_time.is_some() { "Optimal server time" } else { "System time" },
-                                       precise_attack_time.format("%H:%M:%S.%3f UTC")),
-                        });
-                        
-                        // Wait until the precise moment if we're still early
-                        let wait_time_ms = first_request_time_ms - current_time_ms;
-                        if wait_time_ms > 0 {
-                            sleep(Duration::from_millis(wait_time_ms as u64)).await;
-                        }
-                        
-                        let mut tasks = vec![];
-                        for attempt in 1..=8 {
-                            let client = snipe_client.clone();
-                            let logs = logs.clone();
-                            let request_time = precise_attack_time;
-                            let username_clone = username.clone();
-                            let token_clone = access_token.clone();
-                            let task = tokio::spawn(async move {
-                                let actual_send_time = Utc::now();
-                                let url = format!(
-                                    "https://api.minecraftservices.com/minecraft/profile/name/{}",
-                                    username_clone
-                                );
-                                let response = client
-                                    .put(&url)
-                                    .header("Authorization", format!("Bearer {}", token_clone))
-                                    .header("Content-Type", "application/json")
-                                    .body("{}")
-                                    .send()
-                                    .await;
-                                let result = match response {
-                                    Ok(resp) => {
-                                        let status = resp.status();
-                                        if status.is_success() {
-                                            format!("[#{:02}] 🎉 SUCCESS! Status: {} | Planned: {} | Actual: {}", 
-                                                   attempt, status, request_time.format("%H:%M:%S.%3f"), actual_send_time.format("%H:%M:%S.%3f"))
-                                        } else {
-                                            format!("[#{:02}] ❌ Failed - Status: {} | Planned: {} | Actual: {}", 
-                                                   attempt, status, request_time.format("%H:%M:%S.%3f"), actual_send_time.format("%H:%M:%S.%3f"))
-                                        }
-                                    }
-                                    Err(e) => format!("[#{:02}] ❌ Network error: {} | Planned: {} | Actual: {}", 
-                                                     attempt, e, request_time.format("%H:%M:%S.%3f"), actual_send_time.format("%H:%M:%S.%3f")),
-                                };
-                                logs.lock().unwrap().push(LogLine { msg: result });
-                            });
-                            tasks.push(task);
-                            sleep(Duration::from_millis(2)).await;
-                        }
-                        
-                        for task in tasks {
-                            let _ = task.await;
-                        }
-                        
-                        let final_time = Utc::now();
-                        let time_source_final = if cached_time.is_some() { "optimal server" } else { "system" };
-                        logs.lock().unwrap().push(LogLine {
-                            msg: format!("🏁 Snipe sequence completed at: {} ({}) | Actual offset: {}ms", 
-                                       final_time.format("%H:%M:%S.%3f"),
-                                       time_source_final,
-                                       final_time.timestamp_millis() - release_dt.timestamp_millis()),
-                        });
-                        break;
+                    JobState::Upcoming => (egui::Color32::WHITE, job.state.label().to_string()),
+                    JobState::Armed => (egui::Color32::YELLOW, job.state.label().to_string()),
+                    _ => (egui::Color32::GRAY, match &job.result {
+                        Some(result) => format!("{}: {}", job.state.label(), result),
+                        None => job.state.label().to_string(),
+                    }),
+                };
+                ui.colored_label(color, text);
+
+                ui.horizontal(|ui| {
+                    if job.state == JobState::Upcoming && ui.small_button("✖").on_hover_text("Cancel").clicked() {
+                        cancel = Some(job.id);
                     }
-                    
-                    if time_diff_ms < 1000 {
-                        sleep(Duration::from_millis(1)).await;   
-                    } else if time_diff_ms < 5000 {
-                        sleep(Duration::from_millis(10)).await;  
-                    } else if time_diff_ms < 30000 {
-                        sleep(Duration::from_millis(100)).await; 
-                    } else {
-                        sleep(Duration::from_secs(1)).await;
+                    if job.state != JobState::Armed && ui.small_button("🗑").on_hover_text("Remove").clicked() {
+                        remove = Some(job.id);
                     }
-                }
-                
-                // Reset status when done
-                logs.lock().unwrap().push(LogLine {
-                    msg: "🔄 Snipe mission completed. Ready for next operation.".to_string(),
                 });
-            });
+                ui.end_row();
+            }
         });
+
+        if let Some(id) = cancel {
+            self.scheduler.lock().unwrap().set_state(id, JobState::Cancelled, None);
+            self.save_jobs();
+        }
+        if let Some(id) = remove {
+            self.scheduler.lock().unwrap().remove(id);
+            self.save_jobs();
+        }
+    }
+
+    fn show_log(&mut self, ui: &mut egui::Ui) {
+        // The services runtime pumps the recorder too; hold the lock only while drawing.
+        let recorder = self.recorder.clone();
+        let recorder = recorder.lock().unwrap();
+        let history = &recorder.history;
+        ui.horizontal(|ui| {
+            ui.label("📋 Activity Log:");
+            ComboBox::from_id_source("log_level")
+                .selected_text(format!("≥ {}", self.log_min_level.label()))
+                .show_ui(ui, |ui| {
+                    for level in Level::ALL {
+                        ui.selectable_value(&mut self.log_min_level, level, level.label());
+                    }
+                });
+            ComboBox::from_id_source("log_category")
+                .selected_text(self.log_category.map_or("All categories", |category| category.label()))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.log_category, None, "All categories");
+                    for category in Category::ALL {
+                        ui.selectable_value(&mut self.log_category, Some(category), category.label());
+                    }
+                });
+            let dropped = recorder.dropped();
+            if dropped > 0 {
+                ui.colored_label(egui::Color32::YELLOW, format!("{} events dropped (log buffer full)", dropped));
+            }
+            if ui.button("💾 Export recent log")
+                .on_hover_text(format!("Exports the last {} events; the logs folder has every event of each mission", mission_log::HISTORY_LIMIT))
+                .clicked()
+            {
+                self.export_log(history.iter().cloned().collect());
+            }
+            if ui.button("📂 Open logs folder").clicked() {
+                if let Err(e) = mission_log::open_folder(&paths::missions_dir()) {
+                    self.log(LogEvent::error(Category::General, format!("❌ Could not open {}: {}", paths::missions_dir().display(), e)));
+                }
+            }
+        });
+
+        let needle = self.log_search.trim().to_lowercase();
+        let visible: Vec<usize> = history.iter().enumerate()
+            .filter(|(_, event)| event.level >= self.log_min_level)
+            .filter(|(_, event)| self.log_category.is_none_or(|category| event.category == category))
+            .filter(|(_, event)| event.matches(&needle))
+            .map(|(index, _)| index)
+            .collect();
+        let selected = |id: u64, selection: Option<(u64, u64)>| {
+            selection.is_some_and(|(anchor, end)| (anchor.min(end)..=anchor.max(end)).contains(&id))
+        };
+
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.log_search).hint_text("🔍 Search").desired_width(200.0));
+            ui.checkbox(&mut self.log_follow, "Follow")
+                .on_hover_text("Keep the newest event in view");
+            ui.weak(format!("{} of the last {} events", visible.len(), history.len()));
+
+            let selection = self.log_selection;
+            let selected_lines: Vec<String> = visible.iter()
+                .filter(|&&index| selected(recorder.evicted + index as u64, selection))
+                .map(|&index| history[index].to_text())
+                .collect();
+            if ui.add_enabled(!selected_lines.is_empty(), egui::Button::new("📋 Copy selection")).clicked() {
+                ui.output_mut(|output| output.copied_text = selected_lines.join("\n"));
+            }
+            if ui.button("📋 Copy all").on_hover_text(format!("Copies the events matching the filters among the last {}", mission_log::HISTORY_LIMIT)).clicked() {
+                let lines: Vec<String> = visible.iter().map(|&index| history[index].to_text()).collect();
+                ui.output_mut(|output| output.copied_text = lines.join("\n"));
+            }
+        });
+
+        // Rows are laid out only while on screen, so a long history costs nothing to draw.
+        let row_height = ui.text_style_height(&egui::TextStyle::Monospace) + ui.spacing().item_spacing.y;
+        let shift = ui.input(|input| input.modifiers.shift);
+        let mut clicked = None;
+        egui::ScrollArea::both()
+            .auto_shrink([false; 2])
+            .max_height(350.0)
+            .stick_to_bottom(self.log_follow)
+            .show_rows(ui, row_height, visible.len(), |ui, rows| {
+                ui.style_mut().wrap = Some(false);
+                for &index in &visible[rows] {
+                    let event = &history[index];
+                    let id = recorder.evicted + index as u64;
+                    let mut line = format!("{} {:<5} [{}] {}",
+                        event.timestamp.format("%H:%M:%S%.3f"), event.level.label(), event.category.label(), event.message);
+                    if !event.fields.is_empty() {
+                        line.push_str("  ");
+                        line.push_str(&event.fields_text());
+                    }
+                    let text = egui::RichText::new(line).monospace().color(event.level.color());
+                    let row = ui.selectable_label(selected(id, self.log_selection), text);
+                    if row.clicked() {
+                        clicked = Some(id);
+                    }
+                }
+            });
+
+        if let Some(id) = clicked {
+            self.log_selection = match self.log_selection {
+                Some((anchor, _)) if shift => Some((anchor, id)),
+                Some((anchor, end)) if anchor == id && end == id => None,
+                _ => Some((id, id)),
+            };
+        }
+    }
+
+    fn launch(&mut self, params: MissionParams) {
+        if self.mission_running.swap(true, Ordering::SeqCst) {
+            self.log(LogEvent::error(Category::Mission, "❌ A mission is already running!"));
+            return;
+        }
+        *self.outcome.lock().unwrap() = None;
+        self.mission_started();
+        if !self.send(Command::StartMission(params)) {
+            self.is_running = false;
+            self.mission_running.store(false, Ordering::SeqCst);
+        }
+    }
+
+    /// Marks a mission as running; also called when the job keeper arms a job.
+    fn mission_started(&mut self) {
+        self.status = "In progress...".into();
+        self.is_running = true;
     }
 }
 
@@ -451,14 +1313,45 @@ impl App for SnipeApp {
         self.current_utc_time = Utc::now();
         
         // Reset status when not running
-        if !self.is_running {
-            if self.status == "In progress..." {
-                self.status = "Ready".to_string();
+        if !self.is_running && self.status == "In progress..." {
+            self.status = "Ready".to_string();
+        }
+        
+        self.handle_events();
+        if self.services.is_none() {
+            // Nothing else reads the log pipeline.
+            self.recorder.lock().unwrap().pump();
+        }
+        self.publish_typed_token();
+        
+        if self.vault_prompt {
+            self.show_vault_prompt(ctx);
+        }
+        
+        let countdown = self.countdown.lock().unwrap().clone();
+        self.update_big_clock(ctx, countdown.as_ref());
+        if let Some(countdown) = &countdown {
+            // Milliseconds are on screen; repaint every frame until the mission ends.
+            ctx.request_repaint();
+            if self.big_clock {
+                self.show_big_clock(ctx, countdown);
+                return;
             }
         }
         
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.heading("🎯 SnipeBot - Minecraft Username Sniper");
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut self.tab, Tab::Mission, "🎯 Mission");
+                ui.selectable_value(&mut self.tab, Tab::History, "📚 History");
+                ui.selectable_value(&mut self.tab, Tab::Stats, "📊 Stats");
+            });
+            ui.separator();
+            match self.tab {
+                Tab::History => return self.show_history(ui),
+                Tab::Stats => return self.show_stats(ui),
+                Tab::Mission => {}
+            }
             ui.add_space(10.0);
             
             // Info section
@@ -467,16 +1360,26 @@ impl App for SnipeApp {
                 ui.separator();
                 ui.label("• Automatic synchronization with optimal server selection (minimal latency)");
                 ui.label("• Time consistency check between servers");
-                ui.label("• Sends 5 simultaneous requests to maximize chances");
+                ui.label("• Sends a configurable burst of requests, capped by the per-account rate limit");
+                ui.label("• Pre-resolves the API edge IPs and pins the burst to the fastest ones");
                 ui.label("• Customizable timing from 0 to 1000ms before release");
-                ui.label("• Enter the release time in format YYYY-MM-DDTHH:MM:SS");
+                ui.label("• Drop window mode paces attempts around an uncertain release, densest at its centre");
+                ui.label("• Release time: UTC date/time (ms allowed), a zone such as 'local' or 'Europe/Paris', unix s/ms, or 'in 10m'");
                 ui.label("• Access token: Obtain it from your Minecraft profile");
                 ui.label("• Refer to the current UTC time for timing");
             });
             ui.add_space(10.0);
             
             ui.group(|ui| {
-                ui.label("⚙️ Configuration");
+                ui.horizontal(|ui| {
+                    ui.label("⚙️ Configuration");
+                    if ui.add_enabled(!self.is_running, Button::new("↩️ Reset to defaults"))
+                        .on_hover_text("Restore every saved setting and the window layout; tokens are kept")
+                        .clicked()
+                    {
+                        self.reset_settings(ui.ctx());
+                    }
+                });
                 ui.add_space(5.0);
                 
                 ui.horizontal(|ui| {
@@ -486,26 +1389,162 @@ impl App for SnipeApp {
                         .desired_width(150.0));
                 });
                 
+                ui.horizontal(|ui| {
+                    ui.label("👤 Account:");
+                    ui.add(TextEdit::singleline(&mut self.account_name)
+                        .hint_text("Label for this token...")
+                        .desired_width(150.0));
+                });
+                
                 ui.horizontal(|ui| {
                     ui.label("🔑 Access Token:");
                     ui.add(TextEdit::singleline(&mut self.access_token)
                         .hint_text("Enter your Minecraft token...")
                         .password(true)
                         .desired_width(200.0));
+                    let from_vault = self.access_token.trim().is_empty()
+                        && self.credentials.lock().unwrap().vault.as_ref().is_some_and(|vault| vault.get(self.account_name.trim()).is_some());
+                    if from_vault {
+                        ui.colored_label(egui::Color32::LIGHT_GREEN, "🔐 from vault");
+                    }
                 });
                 
                 ui.horizontal(|ui| {
                     ui.label("⏱️ Timing first request:");
-                    ui.add(Slider::new(&mut self.ms_offset, 0..=1000)
+                    ui.add(Slider::new(&mut self.ms_offset, MS_OFFSET_RANGE)
                         .suffix(" ms")
                         .text("ms before release"));
                 });
                 
+                ui.horizontal(|ui| {
+                    ui.label("💥 Burst size:");
+                    ui.add(Slider::new(&mut self.burst_count, BURST_RANGE).suffix(" requests"));
+                });
+                
+                ui.horizontal(|ui| {
+                    ui.label("🪟 Drop window:");
+                    ui.add(DragValue::new(&mut self.drop_window_secs).clamp_range(0..=MAX_DROP_WINDOW_SECS).prefix("± ").suffix(" s"));
+                    ui.label(if self.drop_window_secs == 0 {
+                        "exact time - single burst"
+                    } else {
+                        "attempts paced across the window"
+                    });
+                });
+                
+                ui.horizontal(|ui| {
+                    ui.label("🎯 Planned coverage:");
+                    let uncertainty_ms = self.clock_uncertainty_ms();
+                    let measured = self.clock.lock().unwrap().estimate.is_synced();
+                    if self.drop_window_secs > 0 {
+                        ui.label(format!("window ±{}s, clock ±{}ms", self.drop_window_secs, uncertainty_ms));
+                    } else {
+                        let plan = burst::plan(self.burst_count as usize, uncertainty_ms);
+                        ui.label(burst::coverage_label(&plan, uncertainty_ms));
+                    }
+                    if !measured {
+                        ui.colored_label(egui::Color32::YELLOW, "(assumed clock error)");
+                    }
+                    if ui.add_enabled(!self.is_running, Button::new("🔄 Sync clock")).clicked() {
+                        self.sync_clock();
+                    }
+                });
+                
+                ui.horizontal(|ui| {
+                    ui.label("🚦 Account budget:");
+                    let mut budget = self.limiter.budget();
+                    let mut window_secs = budget.window.as_secs();
+                    ui.add(DragValue::new(&mut budget.requests).clamp_range(1..=100).suffix(" req"));
+                    ui.label("per");
+                    ui.add(DragValue::new(&mut window_secs).clamp_range(1..=3600).suffix(" s"));
+                    budget.window = Duration::from_secs(window_secs);
+                    if budget != self.limiter.budget() {
+                        self.limiter.set_budget(budget);
+                    }
+                });
+                
+                if let Some(access_token) = self.token_for(self.account_name.trim()) {
+                    let account = rate_limit::account_key(&access_token);
+                    let budget = self.limiter.budget();
+                    if let Some(wait) = self.limiter.blocked_for(&account) {
+                        ui.colored_label(egui::Color32::RED,
+                            format!("⛔ Account rate limited by the API (Retry-After) for another {}s", wait.as_secs() + 1));
+                    } else if self.drop_window_secs == 0 {
+                        // The pre-flight check runs at launch; it still counts against the burst
+                        // when the first send is less than a budget window away.
+                        let now = Utc::now();
+                        let until_send = self.parse_release_time().ok()
+                            .and_then(|release| (release - chrono::Duration::milliseconds(self.ms_offset) - now).to_std().ok())
+                            .unwrap_or_default();
+                        let preflight = if until_send < budget.window { mission::PREFLIGHT_REQUESTS } else { 0 };
+                        let sendable = self.limiter.permits_at(&account, std::time::Instant::now() + until_send)
+                            .saturating_sub(preflight);
+                        if self.burst_count as usize > sendable {
+                            ui.colored_label(egui::Color32::YELLOW,
+                                format!("⚠️ Only {} of {} burst requests fit the budget of {} per {}s ({} for the pre-flight check, the rest already spent)",
+                                        sendable, self.burst_count, budget.requests, budget.window.as_secs(), preflight));
+                        }
+                    }
+                }
+                
                 ui.horizontal(|ui| {
                     ui.label("🕐 Custom release time:");
                     ui.add(TextEdit::singleline(&mut self.custom_release_time)
-                        .hint_text("YYYY-MM-DDTHH:MM:SS")
-                        .desired_width(200.0));
+                        .hint_text(release_time::FORMAT_HINT)
+                        .desired_width(300.0));
+                });
+                
+                if !self.custom_release_time.trim().is_empty() {
+                    match self.parse_release_time() {
+                        Ok(release) => ui.colored_label(egui::Color32::LIGHT_GREEN, format!("   → {}", release_time::describe(release))),
+                        Err(e) => ui.colored_label(egui::Color32::RED, format!("   ✖ {}", e)),
+                    };
+                }
+                
+                ui.horizontal(|ui| {
+                    ui.label("🛰️ Edge routing:");
+                    ComboBox::from_id_source("edge_mode")
+                        .selected_text(self.edge_mode.label())
+                        .show_ui(ui, |ui| {
+                            for mode in [EdgeMode::System, EdgeMode::Fastest, EdgeMode::Spread(2), EdgeMode::Spread(3)] {
+                                ui.selectable_value(&mut self.edge_mode, mode, mode.label());
+                            }
+                        });
+                    if ui.add_enabled(!self.is_running, Button::new("📡 Probe")).clicked() {
+                        self.probe_edges();
+                    }
+                });
+                
+                {
+                    let edges = self.edges.lock().unwrap();
+                    if !edges.is_empty() {
+                        let chosen = dns::select_edges(&edges, self.edge_mode);
+                        for probe in edges.iter() {
+                            let rtt = probe.rtt
+                                .map(|rtt| format!("{:.1}ms", rtt.as_secs_f64() * 1000.0))
+                                .unwrap_or_else(|| "unreachable".to_string());
+                            let line = format!("{} {:<40} {}", if chosen.contains(&probe.addr) { "▶" } else { " " }, probe.addr, rtt);
+                            if chosen.contains(&probe.addr) {
+                                ui.colored_label(egui::Color32::GREEN, egui::RichText::new(line).monospace());
+                            } else {
+                                ui.monospace(line);
+                            }
+                        }
+                    }
+                }
+                
+                ui.horizontal(|ui| {
+                    ui.label("⚡ Firing:");
+                    ui.add_enabled_ui(firing::supported(), |ui| {
+                        ui.checkbox(&mut self.firing.dedicated, "Dedicated thread")
+                            .on_disabled_hover_text("Only available on Linux");
+                        ui.add_enabled_ui(self.firing.dedicated, |ui| {
+                            let mut pin = self.firing.cpu.is_some();
+                            ui.checkbox(&mut pin, "Pin to CPU");
+                            ui.add_enabled(pin, DragValue::new(&mut self.firing_cpu).clamp_range(0..=MAX_FIRING_CPU));
+                            self.firing.cpu = pin.then_some(self.firing_cpu);
+                            ui.checkbox(&mut self.firing.realtime, "Real-time priority");
+                        });
+                    });
                 });
                 
                 ui.add_space(5.0);
@@ -516,6 +1555,31 @@ impl App for SnipeApp {
                 });
             });
             
+            egui::CollapsingHeader::new("🕰️ Clock")
+                .id_source("clock")
+                .default_open(true)
+                .show(ui, |ui| self.show_clock(ui));
+            
+            egui::CollapsingHeader::new("🔐 Token vault")
+                .id_source("vault")
+                .show(ui, |ui| self.show_vault(ui));
+            
+            egui::CollapsingHeader::new("🌐 Endpoints")
+                .id_source("endpoints")
+                .show(ui, |ui| self.show_endpoints(ui));
+            
+            egui::CollapsingHeader::new("🧮 Drop-time calculator")
+                .id_source("drop_calculator")
+                .show(ui, |ui| self.show_drop_calculator(ui));
+            
+            egui::CollapsingHeader::new("📈 Offset tuning")
+                .id_source("tuning")
+                .show(ui, |ui| self.show_tuning(ui));
+            
+            egui::CollapsingHeader::new("🎲 Hit-probability simulator")
+                .id_source("simulator")
+                .show(ui, |ui| self.show_simulator(ui));
+            
             ui.add_space(15.0);
             ui.spacing_mut().button_padding = Vec2::new(25.0, 15.0);
             
@@ -533,18 +1597,29 @@ impl App for SnipeApp {
                     egui::Color32::from_rgb(0, 150, 0) 
                 });
             
-            ui.add_enabled_ui(!self.is_running, |ui| {
-                if ui.add(start_button).clicked() {
-                    self.start_snipe();
+            ui.horizontal(|ui| {
+                ui.add_enabled_ui(!self.is_running, |ui| {
+                    if ui.add(start_button).clicked() {
+                        self.start_snipe();
+                    }
+                });
+                if ui.add(Button::new("🗓️ Schedule Job").min_size(Vec2::new(150.0, 50.0))).clicked() {
+                    self.schedule_job();
+                }
+                if ui.add(Button::new("🩺 Pre-flight check").min_size(Vec2::new(150.0, 50.0))).clicked() {
+                    self.run_preflight();
                 }
             });
             
             ui.add_space(10.0);
             
+            if let Some(countdown) = &countdown {
+                self.show_countdown(ui, countdown);
+            }
+            
             // Show release time info if custom time is set
             if !self.custom_release_time.trim().is_empty() {
-                if let Ok(parsed_time) = DateTime::parse_from_rfc3339(&format!("{}Z", self.custom_release_time)) {
-                    let release_utc = parsed_time.with_timezone(&Utc);
+                if let Ok(release_utc) = self.parse_release_time() {
                     let time_diff = release_utc.signed_duration_since(self.current_utc_time);
 
                     ui.horizontal(|ui| {
@@ -576,40 +1651,95 @@ impl App for SnipeApp {
                 );
             });
             
-            ui.separator();
-            ui.label("📋 Activity Log:");
-            
-            egui::ScrollArea::vertical()
-                .auto_shrink([false; 2])
-                .max_height(350.0)
-                .show(ui, |ui| {
-                    let logs = self.logs.lock().unwrap();
-                    for log in logs.iter().rev().take(50) {
-                        ui.label(&log.msg);
-                    }
+            if let Some(outcome) = self.outcome.lock().unwrap().as_ref() {
+                ui.horizontal(|ui| {
+                    ui.label("🏁 Result:");
+                    let color = match outcome {
+                        MissionOutcome::Claimed { .. } => egui::Color32::GREEN,
+                        MissionOutcome::Unconfirmed { .. } => egui::Color32::from_rgb(255, 165, 0),
+                        _ => egui::Color32::RED,
+                    };
+                    ui.colored_label(color, outcome.label());
                 });
-        });
-        
-        // Auto-reset is_running flag when task completes
-        {
-            let logs = self.logs.lock().unwrap();
-            if let Some(last_log) = logs.last() {
-                if last_log.msg.contains("Snipe mission completed") && self.is_running {
-                    self.is_running = false;
-                }
             }
-        }
+            
+            if let Some(record) = self.history.first().filter(|record| record.fired()) {
+                egui::CollapsingHeader::new(format!("📍 Last mission timeline - {} ({})", record.target, record.started_at.format("%Y-%m-%d %H:%M")))
+                    .id_source("last_timeline")
+                    .default_open(true)
+                    .show(ui, |ui| timeline::show(ui, record));
+            }
+            
+            let pending = self.scheduler.lock().unwrap().jobs.iter()
+                .filter(|job| matches!(job.state, JobState::Upcoming | JobState::Armed))
+                .count();
+            egui::CollapsingHeader::new(format!("🗓️ Scheduled jobs ({} pending)", pending))
+                .id_source("scheduled_jobs")
+                .show(ui, |ui| self.show_jobs(ui));
+            
+            ui.separator();
+            self.show_log(ui);
+        });
         
         ctx.request_repaint_after(Duration::from_millis(50));
     }
+
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, settings::STORAGE_KEY, &self.settings());
+    }
+}
+
+/// `T-mm:ss.mmm` before the first request, `T+` after it; hours are added when needed.
+fn format_countdown(remaining_ms: i64) -> String {
+    let sign = if remaining_ms > 0 { "T-" } else { "T+" };
+    let ms = remaining_ms.abs();
+    let (hours, minutes, seconds, millis) = (ms / 3_600_000, ms / 60_000 % 60, ms / 1000 % 60, ms % 1000);
+    if hours > 0 {
+        format!("{}{}:{:02}:{:02}.{:03}", sign, hours, minutes, seconds, millis)
+    } else {
+        format!("{}{:02}:{:02}.{:03}", sign, minutes, seconds, millis)
+    }
+}
+
+fn countdown_color(remaining_ms: i64) -> egui::Color32 {
+    if remaining_ms <= 0 {
+        egui::Color32::GREEN
+    } else if remaining_ms <= 10_000 {
+        egui::Color32::from_rgb(255, 165, 0)
+    } else if remaining_ms <= BIG_CLOCK_FROM_MS {
+        egui::Color32::YELLOW
+    } else {
+        egui::Color32::WHITE
+    }
+}
+
+fn synced_time_text(now_ms: i64) -> String {
+    DateTime::from_timestamp_millis(now_ms)
+        .map(|dt| dt.format("%H:%M:%S%.3f UTC").to_string())
+        .unwrap_or_default()
 }
 
 fn main() -> eframe::Result<()> {
-    let app = SnipeApp::new();
+    let args: Vec<String> = std::env::args().collect();
+    let report = match args.get(1).map(String::as_str) {
+        Some("simulate") => Some(simulator::run_cli(&args[2..])),
+        Some("history") => Some(history::run_cli(&paths::history_db(), &args[2..])),
+        _ => None,
+    };
+    if let Some(report) = report {
+        match report {
+            Ok(report) => println!("{}", report),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(2);
+            }
+        }
+        return Ok(());
+    }
     
     let options = eframe::NativeOptions {
         viewport: ViewportBuilder::default()
-            .with_inner_size([650.0, 850.0])
+            .with_inner_size(WINDOW_SIZE)
             .with_title("SnipeBot - Minecraft Username Sniper"),
         ..Default::default()
     };
@@ -617,6 +1747,6 @@ fn main() -> eframe::Result<()> {
     eframe::run_native(
         "SnipeBot - Minecraft Username Sniper",
         options,
-        Box::new(|_cc: &CreationContext| Box::new(app)),
+        Box::new(|cc: &CreationContext| Box::new(SnipeApp::new(cc.storage))),
     )
 }
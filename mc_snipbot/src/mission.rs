@@ -0,0 +1,705 @@
+use crate::burst;
+use crate::clock_sync::{self, ClockTracker, TimeSync};
+use crate::dns::{self, EdgeMode, EdgeProbe};
+use crate::firing::{self, FiringOptions};
+use crate::history::{self, MissionRecord};
+use crate::mission_log::Recorder;
+use crate::mojang;
+use crate::postmortem;
+use crate::settings::Endpoints;
+use crate::paths;
+use crate::rate_limit::{self, LimitError, RateLimiter};
+use crate::scheduler::Scheduler;
+use crate::vault::{Credentials, SecretToken};
+use crate::window;
+use crate::logging::{Category, LogEvent, LogSink};
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::sleep;
+
+const VERIFY_ATTEMPTS: usize = 3;
+const VERIFY_RETRY_DELAY: Duration = Duration::from_secs(2);
+/// When a window is already open at planning time, its first send is at least this far out.
+const WINDOW_REPLAN_LEAD_MS: i64 = 1_000;
+/// The dedicated firing thread is started this long before the first send, so spawning it and
+/// setting its priority and affinity stay off the first deadline. The clock is frozen by then.
+const FIRING_THREAD_LEAD_MS: i64 = 1_000;
+
+/// What happened to a single request of the burst.
+#[derive(Clone)]
+pub struct AttemptRecord {
+    pub attempt: usize,
+    pub planned: DateTime<Utc>,
+    pub sent: DateTime<Utc>,
+    pub responded: Option<DateTime<Utc>>,
+    pub status: Option<u16>,
+    pub body: String,
+    pub error: Option<String>,
+    /// `Date` header of the response.
+    pub server_date: Option<DateTime<Utc>>,
+}
+
+impl AttemptRecord {
+    pub fn is_success(&self) -> bool {
+        self.status.is_some_and(|s| (200..300).contains(&s))
+    }
+
+    /// Status code, transport error or "no response".
+    pub fn result_label(&self) -> String {
+        match (self.status, &self.error) {
+            (Some(status), _) => status.to_string(),
+            (None, Some(error)) => error.clone(),
+            (None, None) => "no response".to_string(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum MissionOutcome {
+    Claimed { uuid: String, name: String },
+    Unconfirmed { evidence: String },
+    NotClaimed,
+    Aborted(String),
+}
+
+impl MissionOutcome {
+    pub fn label(&self) -> String {
+        match self {
+            MissionOutcome::Claimed { uuid, name } => format!("Claimed '{}' ({})", name, uuid),
+            MissionOutcome::Unconfirmed { evidence } => format!("Unconfirmed - {}", evidence),
+            MissionOutcome::NotClaimed => "Not claimed".to_string(),
+            MissionOutcome::Aborted(reason) => format!("Aborted - {}", reason),
+        }
+    }
+
+    /// Stable name of the variant, as stored in the mission history.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            MissionOutcome::Claimed { .. } => "claimed",
+            MissionOutcome::Unconfirmed { .. } => "unconfirmed",
+            MissionOutcome::NotClaimed => "not_claimed",
+            MissionOutcome::Aborted(_) => "aborted",
+        }
+    }
+}
+
+/// How early and how hard a mission fires.
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct TimingProfile {
+    pub ms_offset: i64,
+    pub burst_count: u8,
+    /// Half-width of the drop window; 0 fires a single burst at the exact release time.
+    #[serde(default)]
+    pub window_ms: u32,
+}
+
+/// Everything that identifies one snipe attempt.
+pub struct MissionParams {
+    pub username: String,
+    /// Label the token was entered or stored under; recorded in the history.
+    pub account: String,
+    pub access_token: SecretToken,
+    pub release_ms: i64,
+    pub timing: TimingProfile,
+    pub edge_mode: EdgeMode,
+    pub firing: FiringOptions,
+    /// Scheduled job the mission was armed for.
+    pub job: Option<u64>,
+}
+
+/// Where the running mission's countdown stands, for the GUI's countdown widget.
+#[derive(Clone)]
+pub struct Countdown {
+    pub username: String,
+    pub release_ms: i64,
+    /// First planned send on the synced clock; moves when the burst is re-planned.
+    pub first_send_ms: i64,
+    /// When the countdown started, the zero point of its progress bar.
+    pub armed_ms: i64,
+}
+
+impl Countdown {
+    /// Share of the countdown elapsed at `now_ms`, from 0 when armed to 1 at the first send.
+    pub fn progress(&self, now_ms: i64) -> f32 {
+        let total = (self.first_send_ms - self.armed_ms).max(1);
+        ((now_ms - self.armed_ms) as f32 / total as f32).clamp(0.0, 1.0)
+    }
+}
+
+/// State shared between a running mission and the GUI.
+#[derive(Clone)]
+pub struct MissionContext {
+    pub logs: LogSink,
+    pub edges: Arc<Mutex<Vec<EdgeProbe>>>,
+    pub limiter: Arc<RateLimiter>,
+    pub outcome: Arc<Mutex<Option<MissionOutcome>>>,
+    pub last_sync: Arc<Mutex<Option<TimeSync>>>,
+    pub clock: Arc<Mutex<ClockTracker>>,
+    /// Set while the mission counts down and fires.
+    pub countdown: Arc<Mutex<Option<Countdown>>>,
+    pub endpoints: Arc<Mutex<Endpoints>>,
+    pub scheduler: Arc<Mutex<Scheduler>>,
+    pub credentials: Arc<Mutex<Credentials>>,
+    /// Set while a mission runs. Claimed with `swap` before `Command::StartMission` is sent,
+    /// so the GUI and the job keeper never start two at once.
+    pub mission_running: Arc<AtomicBool>,
+    pub recorder: Arc<Mutex<Recorder>>,
+}
+
+/// Who and what a single request targets; cheap to clone into spawned attempts.
+#[derive(Clone)]
+struct Target {
+    api_base: String,
+    limiter: Arc<RateLimiter>,
+    account: String,
+    access_token: SecretToken,
+    username: String,
+    logs: LogSink,
+}
+
+pub async fn run(params: MissionParams, ctx: MissionContext) {
+    let MissionContext { logs, edges, limiter, outcome, last_sync: shared_sync, clock, countdown, endpoints, .. } = ctx;
+    let MissionParams { username, account: account_label, access_token, release_ms, timing, edge_mode, firing, .. } = params;
+    let TimingProfile { ms_offset, burst_count, window_ms } = timing;
+    let account = rate_limit::account_key(&access_token);
+    let network = dns::network_id();
+    let endpoints = endpoints.lock().unwrap().clone();
+    let api_base = endpoints.api_base().to_string();
+    let api_host = endpoints.api_host_or_default();
+    let mut mission_record = MissionRecord {
+        started_at: Utc::now(),
+        target: username.clone(),
+        account: account_label,
+        network: network.clone(),
+        release_ms,
+        timing,
+        ..Default::default()
+    };
+    
+    let pinned = if edge_mode == EdgeMode::System {
+        Vec::new()
+    } else {
+        refresh_edges(&logs, &edges, &api_host).await;
+        dns::select_edges(&edges.lock().unwrap(), edge_mode)
+    };
+    let snipe_clients: Vec<Client> = if pinned.is_empty() {
+        vec![build_snipe_client(&api_host, None)]
+    } else {
+        pinned.iter().map(|ip| build_snipe_client(&api_host, Some(*ip))).collect()
+    };
+    logs.push(LogEvent::info(Category::Network, if pinned.is_empty() {
+            "🛰️ Burst routed through the system resolver".to_string()
+        } else {
+            format!("🛰️ Burst pinned to: {}", pinned.iter().map(|ip| ip.to_string()).collect::<Vec<_>>().join(", "))
+        }));
+    let release_dt = DateTime::from_timestamp_millis(release_ms).unwrap_or_else(Utc::now);
+    logs.push(LogEvent::info(Category::Mission, format!("🎯 Target username: {}", username)));
+    
+    if !run_preflight(&snipe_clients[0], &api_base, &limiter, &account, &access_token, &username, &logs).await {
+        let verdict = MissionOutcome::Aborted("pre-flight failed".to_string());
+        save_history(mission_record, &verdict, &clock, &logs);
+        *outcome.lock().unwrap() = Some(verdict);
+        logs.push(LogEvent::info(Category::Mission, "🔄 Snipe mission completed. Ready for next operation.".to_string()));
+        return;
+    }
+    
+    logs.push(LogEvent::info(Category::Mission, format!("🎯 Target release time: {}", release_dt.format("%Y-%m-%d %H:%M:%S%.3f UTC"))));
+    
+    let window_plan = if window_ms > 0 {
+        let window_start_ms = release_ms - window_ms as i64 - ms_offset;
+        let now_ms = Utc::now().timestamp_millis();
+        let earliest_ms = now_ms + WINDOW_REPLAN_LEAD_MS;
+        if earliest_ms > window_start_ms {
+            logs.push(LogEvent::warn(Category::Countdown, format!("⚠️ Window opened {}ms ago - covering only its remaining part",
+                           earliest_ms - window_start_ms)));
+        }
+        let until_start = Duration::from_millis((window_start_ms - now_ms).max(0) as u64);
+        let available = limiter.permits_at(&account, std::time::Instant::now() + until_start);
+        let plan = window::plan(release_ms - ms_offset, window_ms as i64, earliest_ms, limiter.budget(), available);
+        logs.push(LogEvent::info(Category::Countdown, format!("🪟 Window mode: {} attempts across ±{}ms, offsets from release: {}",
+                       plan.len(), window_ms,
+                       plan.iter().map(|t| format!("{:+}", t - release_ms)).collect::<Vec<_>>().join(", "))));
+        if plan.is_empty() {
+            let reason = if earliest_ms > release_ms + window_ms as i64 - ms_offset {
+                "the window has already closed"
+            } else {
+                "no rate-limit budget for the window"
+            };
+            let verdict = MissionOutcome::Aborted(reason.to_string());
+            save_history(mission_record, &verdict, &clock, &logs);
+            *outcome.lock().unwrap() = Some(verdict);
+            logs.push(LogEvent::info(Category::Mission, "🔄 Snipe mission completed. Ready for next operation.".to_string()));
+            return;
+        }
+        plan
+    } else {
+        Vec::new()
+    };
+    let target_send_ms = release_ms - ms_offset;
+    let mut burst_plan = burst::plan(burst_count as usize, 0);
+    let mut first_request_time_ms = window_plan.first().copied().unwrap_or(target_send_ms);
+    let target = Target {
+        api_base: api_base.clone(),
+        limiter: limiter.clone(),
+        account: account.clone(),
+        access_token: access_token.clone(),
+        username: username.clone(),
+        logs: logs.clone(),
+    };
+    logs.push(LogEvent::info(Category::Countdown, format!("🚀 First request will be sent at: {} ({}ms before release)", 
+                   DateTime::from_timestamp_millis(first_request_time_ms).unwrap().format("%H:%M:%S.%3f UTC"),
+                   ms_offset)));
+    
+    *countdown.lock().unwrap() = Some(Countdown {
+        username: username.clone(),
+        release_ms,
+        first_send_ms: first_request_time_ms,
+        armed_ms: clock.lock().unwrap().estimate.now_ms(),
+    });
+    
+    let remaining = || first_request_time_ms - clock.lock().unwrap().estimate.now_ms();
+    if clock_sync::sync_allowed(remaining(), endpoints.time_sources.len()) {
+        match clock_sync::sync_before_freeze(&logs, &endpoints.time_sources, remaining).await {
+            Ok(sync) => {
+                if clock.lock().unwrap().add(&sync) {
+                    *shared_sync.lock().unwrap() = Some(sync);
+                }
+            }
+            Err(e) => logs.push(LogEvent::warn(Category::TimeSync, format!("⚠️ Sync failed: {} - keeping the current clock estimate until a background sync succeeds", e))),
+        }
+    } else {
+        logs.push(LogEvent::warn(Category::TimeSync, "⚠️ Too close to release for a network time sync - using the current clock estimate".to_string()));
+    }
+    let refiner = tokio::spawn(clock_sync::refine(
+        first_request_time_ms,
+        clock.clone(),
+        shared_sync.clone(),
+        endpoints.time_sources.clone(),
+        logs.clone(),
+    ));
+    
+    let mut planned_uncertainty: Option<i64> = None;
+    let mut last_log_time = std::time::Instant::now();
+    
+    loop {
+        // Only ever read the precomputed estimate here; syncing happens in the refiner task.
+        let estimate = clock.lock().unwrap().estimate.clone();
+        let uncertainty_ms = estimate.aged_uncertainty().as_millis() as i64;
+        if window_plan.is_empty() && planned_uncertainty != Some(uncertainty_ms) {
+            let replanned = burst::plan(burst_count as usize, uncertainty_ms);
+            if replanned.spacing_ms != burst_plan.spacing_ms || planned_uncertainty.is_none() {
+                logs.push(LogEvent::info(Category::Countdown, format!("🎯 Burst plan {}", burst::coverage_label(&replanned, uncertainty_ms))));
+            }
+            burst_plan = replanned;
+            first_request_time_ms = target_send_ms + burst_plan.first_offset_ms();
+            planned_uncertainty = Some(uncertainty_ms);
+            if let Some(countdown) = countdown.lock().unwrap().as_mut() {
+                countdown.first_send_ms = first_request_time_ms;
+            }
+        }
+        let current_time_ms = estimate.now_ms();
+        let current_time = DateTime::from_timestamp_millis(current_time_ms).unwrap_or_else(Utc::now);
+        let time_diff_ms = first_request_time_ms - current_time_ms;
+        
+        let log_interval = if time_diff_ms < 5000 {
+            Duration::from_millis(100)  
+        } else if time_diff_ms < 30000 {
+            Duration::from_millis(1000)
+        } else if time_diff_ms < 120000 {
+            Duration::from_millis(3000) 
+        } else {
+            Duration::from_millis(5000)
+        };
+        
+        let should_log = time_diff_ms <= 0 || last_log_time.elapsed() >= log_interval;
+        if should_log {
+            last_log_time = std::time::Instant::now();
+            let time_remaining = if time_diff_ms > 0 {
+                if time_diff_ms >= 60000 {
+                    format!("{}min {}s", time_diff_ms / 60000, (time_diff_ms % 60000) / 1000)
+                } else if time_diff_ms >= 1000 {
+                    format!("{}.{}s", time_diff_ms / 1000, (time_diff_ms % 1000) / 100)
+                } else {
+                    format!("{}ms", time_diff_ms)
+                }
+            } else {
+                "FIRING!".to_string()
+            };
+
+            logs.push(LogEvent::info(Category::Countdown, format!("⏱️ Now: {} | Time to snipe: {}", 
+                           current_time.format("%H:%M:%S.%3f"), time_remaining))
+                .field("remaining_ms", time_diff_ms));
+        }
+        
+        let lead_ms = if firing.dedicated { FIRING_THREAD_LEAD_MS } else { 0 };
+        if current_time_ms >= first_request_time_ms - lead_ms {
+            let synced_now_ms = || estimate.now_ms();
+            let launching = LogEvent::info(Category::Countdown, format!("🚀 LAUNCHING SNIPE ATTACK NOW! ({}ms before release)", ms_offset));
+            
+            let precise_attack_time = DateTime::from_timestamp_millis(first_request_time_ms)
+                .unwrap_or(current_time);
+            
+            logs.push(LogEvent::info(Category::Countdown, format!("📡 Attack time based on: {} | Precise time: {}", 
+                           if estimate.is_synced() { "Optimal server time" } else { "System time" },
+                           precise_attack_time.format("%H:%M:%S.%3f UTC"))));
+            
+            let clock_offset_ms = estimate.clock_offset_ms();
+            let mut tasks = vec![];
+            let mut planned_burst = 0;
+            let claimed = Arc::new(AtomicBool::new(false));
+            let stop_on_success = !window_plan.is_empty();
+            
+            let schedule: Vec<i64> = if window_plan.is_empty() {
+                let first_send = std::time::Instant::now() + Duration::from_millis(time_diff_ms.max(0) as u64);
+                let allowed = limiter.permits_at(&account, first_send);
+                let sendable = (burst_count as usize).min(allowed);
+                if sendable < burst_count as usize {
+                    logs.push(LogEvent::warn(Category::Countdown, format!("⚠️ Rate limiter allows only {} of {} planned requests - burst reduced", sendable, burst_count)));
+                    burst_plan = burst::plan(sendable, uncertainty_ms);
+                }
+                burst_plan.offsets_ms.iter().map(|offset_ms| target_send_ms + offset_ms).collect()
+            } else {
+                window_plan.clone()
+            };
+            
+            let fire = |index: usize| {
+                let client = snipe_clients[index % snipe_clients.len()].clone();
+                let planned = DateTime::from_timestamp_millis(schedule[index]).unwrap_or(precise_attack_time);
+                let target = target.clone();
+                let claimed = claimed.clone();
+                tokio::spawn(async move {
+                    let record = send_attempt(target, client, index + 1, planned).await;
+                    if record.is_success() {
+                        claimed.store(true, Ordering::SeqCst);
+                    }
+                    record
+                })
+            };
+            
+            if firing.dedicated {
+                // Every send, the first included, is an absolute deadline the thread sleeps to.
+                let anchor = std::time::Instant::now();
+                let anchor_ms = synced_now_ms();
+                let deadlines = schedule.iter()
+                    .map(|send_ms| anchor + Duration::from_millis((send_ms - anchor_ms).max(0) as u64))
+                    .collect();
+                let stop = if stop_on_success { claimed.clone() } else { Arc::new(AtomicBool::new(false)) };
+                let (handoff, mut due) = tokio::sync::mpsc::unbounded_channel();
+                let report = firing::spawn(deadlines, firing, stop, handoff);
+                logs.push(LogEvent::info(Category::Countdown, format!("⚡ Firing thread armed {}ms before the first send",
+                               schedule.first().map_or(0, |send_ms| send_ms - anchor_ms))));
+                while let Some(index) = due.recv().await {
+                    if index == 0 {
+                        logs.push(launching.clone());
+                    }
+                    planned_burst += 1;
+                    tasks.push(fire(index));
+                }
+                match report.await {
+                    Ok(report) => logs.push(LogEvent::info(Category::Countdown, format!("⚡ Firing thread {}", report.summary()))),
+                    Err(_) => logs.push(LogEvent::error(Category::Mission, "❌ Firing thread panicked".to_string())),
+                }
+            } else {
+                // Wait until the precise moment if we're still early
+                let wait_time_ms = first_request_time_ms - current_time_ms;
+                if wait_time_ms > 0 {
+                    sleep(Duration::from_millis(wait_time_ms as u64)).await;
+                }
+                logs.push(launching);
+                for (index, send_ms) in schedule.iter().enumerate() {
+                    let wait_ms = send_ms - synced_now_ms();
+                    if wait_ms > 0 {
+                        sleep(Duration::from_millis(wait_ms as u64)).await;
+                    }
+                    if stop_on_success && claimed.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    planned_burst += 1;
+                    tasks.push(fire(index));
+                }
+            }
+            if stop_on_success && planned_burst < schedule.len() {
+                logs.push(LogEvent::info(Category::Mission, format!("🛑 Success reported - skipping the remaining {} window attempts", schedule.len() - planned_burst)));
+            }
+            
+            let mut attempts = Vec::new();
+            for task in tasks {
+                if let Ok(record) = task.await {
+                    attempts.push(record);
+                }
+            }
+            
+            let final_time = Utc::now();
+            let time_source_final = if estimate.is_synced() { "optimal server" } else { "system" };
+            logs.push(LogEvent::info(Category::Mission, format!("🏁 Snipe sequence completed at: {} ({}) | Actual offset: {}ms", 
+                           final_time.format("%H:%M:%S.%3f"),
+                           time_source_final,
+                           final_time.timestamp_millis() - release_dt.timestamp_millis())));
+            
+            if attempts.iter().any(|a| a.is_success()) {
+                logs.push(LogEvent::info(Category::Mission, "🔍 Success reported - verifying the profile name...".to_string()));
+            }
+            let verdict = verify_claim(&snipe_clients[0], &api_base, &limiter, &account, &access_token, &username, &attempts).await;
+            logs.push(match &verdict {
+                MissionOutcome::Claimed { .. } => LogEvent::success(Category::Mission, format!("🏆 {}", verdict.label())),
+                MissionOutcome::Unconfirmed { .. } => LogEvent::warn(Category::Mission, format!("⚠️ {}", verdict.label())),
+                _ => LogEvent::error(Category::Mission, format!("❌ {}", verdict.label())),
+            });
+            
+            if matches!(verdict, MissionOutcome::NotClaimed) && postmortem::is_duplicate(&attempts) {
+                let sync_source = estimate.source.clone();
+                let sync_uncertainty_ms = estimate.is_synced().then_some(estimate.uncertainty.as_millis() as i64);
+                let report = postmortem::PostMortem::build(
+                    &snipe_clients[0],
+                    &limiter,
+                    &username,
+                    release_dt,
+                    &attempts,
+                    clock_offset_ms,
+                    postmortem::BurstSettings {
+                        ms_offset,
+                        burst_size: planned_burst,
+                        spacing_ms: if window_plan.is_empty() { burst_plan.spacing_ms as u64 } else { 0 },
+                        window_ms,
+                    },
+                    &sync_source,
+                    sync_uncertainty_ms,
+                ).await;
+                logs.push(LogEvent::info(Category::Mission, format!("🧾 Post-mortem: {}", report.summary())));
+                match report.save(&paths::missions_dir()) {
+                    Ok(path) => logs.push(LogEvent::info(Category::Mission, format!("💾 Post-mortem saved to {}", path.display()))),
+                    Err(e) => logs.push(LogEvent::warn(Category::Mission, format!("⚠️ Could not save post-mortem: {}", e))),
+                }
+            }
+            mission_record.spacing_ms = if window_plan.is_empty() { burst_plan.spacing_ms } else { 0 };
+            mission_record.attempts = attempts;
+            save_history(mission_record, &verdict, &clock, &logs);
+            *outcome.lock().unwrap() = Some(verdict);
+            break;
+        }
+        
+        if time_diff_ms < 1000 {
+            sleep(Duration::from_millis(1)).await;   
+        } else if time_diff_ms < 5000 {
+            sleep(Duration::from_millis(10)).await;  
+        } else if time_diff_ms < 30000 {
+            sleep(Duration::from_millis(100)).await; 
+        } else {
+            sleep(Duration::from_secs(1)).await;
+        }
+    }
+    
+    refiner.abort();
+    *countdown.lock().unwrap() = None;
+    
+    // Reset status when done
+    logs.push(LogEvent::info(Category::Mission, "🔄 Snipe mission completed. Ready for next operation.".to_string()));
+}
+
+/// Files the mission in the history database with the clock state it ended on.
+/// A failure is logged; it never affects the mission.
+fn save_history(mut record: MissionRecord, verdict: &MissionOutcome, clock: &Mutex<ClockTracker>, logs: &LogSink) {
+    {
+        let tracker = clock.lock().unwrap();
+        let estimate = &tracker.estimate;
+        record.sync_source = estimate.source.clone();
+        record.sync_uncertainty_ms = estimate.is_synced().then_some(estimate.uncertainty.as_millis() as i64);
+        record.clock_offset_ms = estimate.clock_offset_ms();
+        record.drift_ppm = tracker.drift_ppm();
+        record.sync_samples = tracker.sample_count();
+    }
+    record.outcome = verdict.kind().to_string();
+    record.verdict = verdict.label();
+    match history::insert(&paths::history_db(), &record) {
+        Ok(id) => logs.push(LogEvent::debug(Category::Mission, format!("📚 Mission #{} added to history", id))),
+        Err(e) => logs.push(LogEvent::warn(Category::Mission, format!("⚠️ Could not record mission history: {}", e))),
+    }
+}
+
+/// Client for the API at `api_host`; with `pinned`, that host always connects to the given edge.
+pub fn build_snipe_client(api_host: &str, pinned: Option<IpAddr>) -> Client {
+    let mut builder = Client::builder()
+        .timeout(Duration::from_secs(10))
+        .connect_timeout(Duration::from_secs(5))
+        .user_agent("SnipeBot/1.0");
+    if let Some(ip) = pinned {
+        builder = builder.resolve(api_host, dns::pinned_addr(ip));
+    }
+    builder.build().unwrap()
+}
+
+/// Sends one name-change request and records what came back.
+async fn send_attempt(target: Target, client: Client, attempt: usize, planned: DateTime<Utc>) -> AttemptRecord {
+    let actual_send_time = Utc::now();
+    let response = mojang::change_name(&client, &target.api_base, &target.limiter, &target.account, &target.access_token, &target.username).await;
+    let mut record = AttemptRecord {
+        attempt,
+        planned,
+        sent: actual_send_time,
+        responded: None,
+        status: None,
+        body: String::new(),
+        error: None,
+        server_date: None,
+    };
+    let event = match response {
+        Ok(resp) => {
+            let status = resp.status();
+            let retry_after = resp.headers().get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or("none")
+                .to_string();
+            record.responded = Some(Utc::now());
+            record.server_date = resp.headers().get(reqwest::header::DATE)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| DateTime::parse_from_rfc2822(v).ok())
+                .map(|dt| dt.with_timezone(&Utc));
+            record.status = Some(status.as_u16());
+            record.body = resp.text().await.unwrap_or_default();
+            let event = if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                LogEvent::warn(Category::Attempt, format!("[#{:02}] ⛔ Rate limited - Status: {} | Retry-After: {} | Actual: {}", 
+                       attempt, status, retry_after,
+                       actual_send_time.format("%H:%M:%S.%3f")))
+                    .field("retry_after", retry_after)
+            } else if status.is_success() {
+                LogEvent::success(Category::Attempt, format!("[#{:02}] 🎉 SUCCESS! Status: {} | Planned: {} | Actual: {}", 
+                       attempt, status, planned.format("%H:%M:%S.%3f"), actual_send_time.format("%H:%M:%S.%3f")))
+            } else {
+                LogEvent::error(Category::Attempt, format!("[#{:02}] ❌ Failed - Status: {} | Planned: {} | Actual: {}", 
+                       attempt, status, planned.format("%H:%M:%S.%3f"), actual_send_time.format("%H:%M:%S.%3f")))
+            };
+            event.field("status", status.as_u16())
+        }
+        Err(LimitError::Throttled(wait)) => {
+            record.error = Some("limiter budget exhausted".to_string());
+            LogEvent::warn(Category::Attempt, format!("[#{:02}] ⛔ Not sent - limiter budget exhausted (retry in {}ms)", 
+                    attempt, wait.as_millis()))
+        }
+        Err(e) => {
+            record.error = Some(e.to_string());
+            LogEvent::error(Category::Attempt, format!("[#{:02}] ❌ Network error: {} | Planned: {} | Actual: {}", 
+                    attempt, e, planned.format("%H:%M:%S.%3f"), actual_send_time.format("%H:%M:%S.%3f")))
+        }
+    };
+    let lateness_ms = actual_send_time.timestamp_millis() - planned.timestamp_millis();
+    target.logs.push(event.field("attempt", attempt).field("send_vs_planned_ms", lateness_ms));
+    record
+}
+
+/// Requests `run_preflight` spends from the account's budget: the profile and the name check.
+pub const PREFLIGHT_REQUESTS: usize = 2;
+
+/// Validates the token and checks the target name well before the burst.
+/// Returns `false` when the mission cannot succeed and should be aborted.
+pub async fn run_preflight(
+    client: &Client,
+    api_base: &str,
+    limiter: &RateLimiter,
+    account: &str,
+    access_token: &str,
+    username: &str,
+    logs: &LogSink,
+) -> bool {
+    match mojang::fetch_profile(client, api_base, limiter, account, access_token).await {
+        Ok(resp) if resp.status().is_success() => {
+            let profile: serde_json::Value = resp.json().await.unwrap_or_default();
+            logs.push(LogEvent::success(Category::Auth, format!("✅ Pre-flight: token valid for profile '{}'", profile["name"].as_str().unwrap_or("?"))));
+        }
+        Ok(resp) if resp.status() == reqwest::StatusCode::UNAUTHORIZED => {
+            logs.push(LogEvent::error(Category::Auth, "❌ Pre-flight: access token rejected (401) - aborting mission".to_string()));
+            return false;
+        }
+        Ok(resp) => {
+            logs.push(LogEvent::warn(Category::Auth, format!("⚠️ Pre-flight: profile check returned {}", resp.status())));
+        }
+        Err(e) => {
+            logs.push(LogEvent::warn(Category::Auth, format!("⚠️ Pre-flight: profile check failed: {}", e)));
+        }
+    }
+
+    match mojang::name_availability(client, api_base, limiter, account, access_token, username).await {
+        Ok(resp) if resp.status().is_success() => {
+            let availability: serde_json::Value = resp.json().await.unwrap_or_default();
+            logs.push(LogEvent::info(Category::Auth, format!("🔎 Pre-flight: '{}' is currently {}", username, availability["status"].as_str().unwrap_or("UNKNOWN"))));
+        }
+        Ok(resp) => {
+            logs.push(LogEvent::warn(Category::Auth, format!("⚠️ Pre-flight: availability check returned {}", resp.status())));
+        }
+        Err(e) => {
+            logs.push(LogEvent::warn(Category::Auth, format!("⚠️ Pre-flight: availability check failed: {}", e)));
+        }
+    }
+    true
+}
+
+pub async fn refresh_edges(logs: &LogSink, edges: &Arc<Mutex<Vec<EdgeProbe>>>, api_host: &str) {
+    logs.push(LogEvent::info(Category::Network, format!("📡 Resolving and probing {} edges...", api_host)));
+    match dns::probe_edges(api_host).await {
+        Ok(probes) => {
+            for probe in &probes {
+                logs.push(match probe.rtt {
+                    Some(rtt) => LogEvent::info(Category::Network, format!("📡 {} RTT: {:.1}ms", probe.addr, rtt.as_secs_f64() * 1000.0))
+                        .field("rtt_us", rtt.as_micros()),
+                    None => LogEvent::warn(Category::Network, format!("⚠️ {} unreachable", probe.addr)),
+                });
+            }
+            *edges.lock().unwrap() = probes;
+        }
+        Err(e) => {
+            logs.push(LogEvent::warn(Category::Network, format!("⚠️ Edge probe failed: {}", e)));
+        }
+    }
+}
+
+/// Confirms a reported success by reading the account profile back.
+/// Only a profile whose name matches `username` counts as a claim.
+pub async fn verify_claim(
+    client: &Client,
+    api_base: &str,
+    limiter: &RateLimiter,
+    account: &str,
+    access_token: &str,
+    username: &str,
+    attempts: &[AttemptRecord],
+) -> MissionOutcome {
+    let successes: Vec<&AttemptRecord> = attempts.iter().filter(|a| a.is_success()).collect();
+    if successes.is_empty() {
+        return MissionOutcome::NotClaimed;
+    }
+
+    let mut evidence: Vec<String> = successes
+        .iter()
+        .map(|a| format!("#{:02} returned {} {}", a.attempt, a.status.unwrap_or(0), a.body.chars().take(120).collect::<String>()))
+        .collect();
+
+    for check in 1..=VERIFY_ATTEMPTS {
+        match mojang::fetch_profile(client, api_base, limiter, account, access_token).await {
+            Ok(resp) if resp.status().is_success() => {
+                let profile: serde_json::Value = resp.json().await.unwrap_or_default();
+                let name = profile["name"].as_str().unwrap_or_default();
+                let uuid = profile["id"].as_str().unwrap_or_default();
+                if name.eq_ignore_ascii_case(username) && !uuid.is_empty() {
+                    return MissionOutcome::Claimed {
+                        uuid: uuid.to_string(),
+                        name: name.to_string(),
+                    };
+                }
+                evidence.push(format!("profile check {} shows name '{}'", check, name));
+            }
+            Ok(resp) => evidence.push(format!("profile check {} returned {}", check, resp.status())),
+            Err(e) => evidence.push(format!("profile check {} failed: {}", check, e)),
+        }
+        if check < VERIFY_ATTEMPTS {
+            tokio::time::sleep(VERIFY_RETRY_DELAY).await;
+        }
+    }
+
+    MissionOutcome::Unconfirmed {
+        evidence: evidence.join("; "),
+    }
+}
@@ -0,0 +1,227 @@
+use crate::logging::{Category, LogDrain, LogEvent};
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Mission logs kept on disk; older ones are deleted when a new mission starts.
+const MAX_MISSION_LOGS: usize = 30;
+
+/// Events the log viewer keeps in memory; the mission files get every event.
+pub const HISTORY_LIMIT: usize = 5000;
+
+/// A mission's events, written both as plain text and as JSON Lines.
+pub struct MissionLog {
+    text: BufWriter<File>,
+    jsonl: BufWriter<File>,
+    pub text_path: PathBuf,
+}
+
+impl MissionLog {
+    /// Creates `<started>_<username>.log` and `.jsonl` in `dir`, pruning old mission logs first.
+    pub fn create(dir: &Path, username: &str, started: DateTime<Utc>) -> std::io::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        prune(dir, MAX_MISSION_LOGS.saturating_sub(1))?;
+        let stem = format!("{}_{}", started.format("%Y%m%dT%H%M%S"), sanitize(username));
+        let text_path = dir.join(format!("{}.log", stem));
+        Ok(Self {
+            text: BufWriter::new(File::create(&text_path)?),
+            jsonl: BufWriter::new(File::create(dir.join(format!("{}.jsonl", stem)))?),
+            text_path,
+        })
+    }
+
+    pub fn write(&mut self, event: &LogEvent) -> std::io::Result<()> {
+        writeln!(self.text, "{}", event.to_text())?;
+        writeln!(self.jsonl, "{}", event.to_json())
+    }
+
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.text.flush()?;
+        self.jsonl.flush()
+    }
+}
+
+/// Reader of the log pipeline. Pumped from the services runtime, so the running mission's
+/// files get every event even while the window is minimized and the GUI is not drawing.
+#[derive(Default)]
+pub struct Recorder {
+    drain: LogDrain,
+    mission: Option<MissionLog>,
+    /// The most recent events, oldest first, capped at `HISTORY_LIMIT`.
+    pub history: VecDeque<LogEvent>,
+    /// Events evicted from the front of `history`, so `evicted + index` identifies an event.
+    pub evicted: u64,
+}
+
+impl Recorder {
+    pub fn new(drain: LogDrain) -> Self {
+        Self {
+            drain,
+            mission: None,
+            history: VecDeque::new(),
+            evicted: 0,
+        }
+    }
+
+    /// Moves new events from the pipeline into the mission's files and the history.
+    pub fn pump(&mut self) {
+        let events = self.drain.drain();
+        if events.is_empty() {
+            return;
+        }
+        if let Some(mission) = self.mission.as_mut() {
+            let result = events.iter()
+                .try_for_each(|event| mission.write(event))
+                .and_then(|_| mission.flush());
+            if let Err(e) = result {
+                self.mission = None;
+                self.keep(LogEvent::warn(Category::Mission, format!("⚠️ Mission log writing stopped: {}", e)));
+            }
+        }
+        events.into_iter().for_each(|event| self.keep(event));
+    }
+
+    fn keep(&mut self, event: LogEvent) {
+        self.history.push_back(event);
+        if self.history.len() > HISTORY_LIMIT {
+            self.history.pop_front();
+            self.evicted += 1;
+        }
+    }
+
+    /// Opens a mission's log files; events pushed before this call stay out of them.
+    pub fn start_mission(&mut self, dir: &Path, username: &str, started: DateTime<Utc>) -> std::io::Result<()> {
+        self.pump();
+        self.mission = Some(MissionLog::create(dir, username, started)?);
+        Ok(())
+    }
+
+    /// Writes what is still queued and closes the mission's files; returns the text file's path.
+    pub fn finish_mission(&mut self) -> Option<PathBuf> {
+        self.pump();
+        self.mission.take().map(|mission| mission.text_path)
+    }
+
+    /// Events dropped because the pipeline was full.
+    pub fn dropped(&self) -> u64 {
+        self.drain.dropped()
+    }
+}
+
+/// Writes `events` to `<stem>.log` and `<stem>.jsonl` in `dir`; returns the text file's path.
+pub fn export(dir: &Path, stem: &str, events: &[LogEvent]) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let text_path = dir.join(format!("{}.log", stem));
+    let mut text = BufWriter::new(File::create(&text_path)?);
+    let mut jsonl = BufWriter::new(File::create(dir.join(format!("{}.jsonl", stem)))?);
+    for event in events {
+        writeln!(text, "{}", event.to_text())?;
+        writeln!(jsonl, "{}", event.to_json())?;
+    }
+    text.flush()?;
+    jsonl.flush()?;
+    Ok(text_path)
+}
+
+/// Keeps the `keep` newest mission logs in `dir`. Names start with the mission's start time,
+/// so sorting by name is sorting by age.
+fn prune(dir: &Path, keep: usize) -> std::io::Result<()> {
+    let mut stems: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "log"))
+        .map(|path| path.with_extension(""))
+        .collect();
+    stems.sort();
+    let excess = stems.len().saturating_sub(keep);
+    for stem in stems.into_iter().take(excess) {
+        for ext in ["log", "jsonl"] {
+            match std::fs::remove_file(stem.with_extension(ext)) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Makes `name` safe to embed in a file name: anything but ASCII letters, digits and `_` becomes `-`.
+pub fn sanitize(name: &str) -> String {
+    name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '-' }).collect()
+}
+
+/// Opens `dir` in the platform's file manager.
+pub fn open_folder(dir: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let opener = if cfg!(target_os = "windows") {
+        "explorer"
+    } else if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    std::process::Command::new(opener).arg(dir).spawn().map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logging;
+
+    /// A fresh directory under the system temp dir, unique per test and process.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("snipebot-log-test-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn event(n: usize) -> LogEvent {
+        LogEvent::info(Category::Mission, format!("event {}", n))
+    }
+
+    #[test]
+    fn mission_file_gets_events_the_history_evicts() {
+        let dir = temp_dir("evicts");
+        let (logs, drain) = logging::pipeline();
+        let mut recorder = Recorder::new(drain);
+        recorder.start_mission(&dir, "target", Utc::now()).unwrap();
+        let total = HISTORY_LIMIT + 10;
+        for n in 0..total {
+            logs.push(event(n));
+            if n % 1000 == 999 {
+                recorder.pump();
+            }
+        }
+        let path = recorder.finish_mission().unwrap();
+
+        assert_eq!(recorder.dropped(), 0);
+        assert_eq!(recorder.history.len(), HISTORY_LIMIT);
+        assert_eq!(recorder.evicted, 10);
+        assert_eq!(recorder.history.front().unwrap().message, "event 10");
+        let text = std::fs::read_to_string(path).unwrap();
+        assert_eq!(text.lines().count(), total);
+        assert!(text.lines().next().unwrap().ends_with("event 0"));
+    }
+
+    #[test]
+    fn mission_file_covers_only_the_mission() {
+        let dir = temp_dir("bounds");
+        let (logs, drain) = logging::pipeline();
+        let mut recorder = Recorder::new(drain);
+        logs.push(event(0));
+        recorder.start_mission(&dir, "target", Utc::now()).unwrap();
+        logs.push(event(1));
+        // Queued but not yet pumped when the mission ends.
+        logs.push(event(2));
+        let path = recorder.finish_mission().unwrap();
+        logs.push(event(3));
+        recorder.pump();
+
+        let text = std::fs::read_to_string(path).unwrap();
+        let messages: Vec<&str> = text.lines().map(|line| line.rsplit("] ").next().unwrap()).collect();
+        assert_eq!(messages, ["event 1", "event 2"]);
+        assert_eq!(recorder.history.len(), 4);
+        assert!(recorder.finish_mission().is_none());
+    }
+}
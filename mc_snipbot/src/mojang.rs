@@ -0,0 +1,65 @@
+use crate::rate_limit::{LimitError, RateLimiter};
+use reqwest::{Client, Response};
+
+/// Default for `Endpoints::api_base`.
+pub const API_BASE: &str = "https://api.minecraftservices.com";
+pub const PUBLIC_API_BASE: &str = "https://api.mojang.com";
+
+/// Limiter key for unauthenticated endpoints, which are throttled per IP rather than per account.
+pub const PUBLIC_KEY: &str = "public";
+
+/// Claims `username` for the account behind `access_token`. Never waits on the limiter.
+pub async fn change_name(
+    client: &Client,
+    api_base: &str,
+    limiter: &RateLimiter,
+    account: &str,
+    access_token: &str,
+    username: &str,
+) -> Result<Response, LimitError> {
+    let request = client
+        .put(format!("{}/minecraft/profile/name/{}", api_base, username))
+        .header("Authorization", format!("Bearer {}", access_token))
+        .header("Content-Type", "application/json")
+        .body("{}");
+    limiter.send_now(account, request).await
+}
+
+/// Fetches the profile of the account behind `access_token`.
+pub async fn fetch_profile(
+    client: &Client,
+    api_base: &str,
+    limiter: &RateLimiter,
+    account: &str,
+    access_token: &str,
+) -> Result<Response, LimitError> {
+    let request = client
+        .get(format!("{}/minecraft/profile", api_base))
+        .header("Authorization", format!("Bearer {}", access_token));
+    limiter.send(account, request).await
+}
+
+/// Asks whether `username` can currently be claimed by the account behind `access_token`.
+pub async fn name_availability(
+    client: &Client,
+    api_base: &str,
+    limiter: &RateLimiter,
+    account: &str,
+    access_token: &str,
+    username: &str,
+) -> Result<Response, LimitError> {
+    let request = client
+        .get(format!("{}/minecraft/profile/name/{}/available", api_base, username))
+        .header("Authorization", format!("Bearer {}", access_token));
+    limiter.send(account, request).await
+}
+
+/// Looks up the current owner of `username` on the public profile-by-name endpoint.
+pub async fn profile_by_name(
+    client: &Client,
+    limiter: &RateLimiter,
+    username: &str,
+) -> Result<Response, LimitError> {
+    let request = client.get(format!("{}/users/profiles/minecraft/{}", PUBLIC_API_BASE, username));
+    limiter.send(PUBLIC_KEY, request).await
+}
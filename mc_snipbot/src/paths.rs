@@ -0,0 +1,34 @@
+use directories::ProjectDirs;
+use std::path::PathBuf;
+
+/// Root folder for everything SnipeBot persists. Falls back to the working directory.
+pub fn data_dir() -> PathBuf {
+    ProjectDirs::from("", "", "SnipeBot")
+        .map(|dirs| dirs.data_dir().to_path_buf())
+        .unwrap_or_else(|| PathBuf::from("snipebot_data"))
+}
+
+/// Folder holding per-mission artifacts (logs, post-mortems).
+pub fn missions_dir() -> PathBuf {
+    data_dir().join("missions")
+}
+
+/// JSON file holding the scheduled jobs.
+pub fn jobs_file() -> PathBuf {
+    data_dir().join("jobs.json")
+}
+
+/// SQLite database with every mission and its attempts.
+pub fn history_db() -> PathBuf {
+    data_dir().join("history.sqlite3")
+}
+
+/// Encrypted token vault.
+pub fn vault_file() -> PathBuf {
+    data_dir().join("tokens.vault")
+}
+
+/// Folder "Export recent log" writes to.
+pub fn exports_dir() -> PathBuf {
+    data_dir().join("exports")
+}
@@ -0,0 +1,143 @@
+use crate::mission::AttemptRecord;
+use crate::mission_log;
+use crate::mojang;
+use crate::rate_limit::RateLimiter;
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use std::path::{Path, PathBuf};
+
+/// Timing parameters the burst was fired with.
+pub struct BurstSettings {
+    pub ms_offset: i64,
+    pub burst_size: usize,
+    pub spacing_ms: u64,
+    pub window_ms: u32,
+}
+
+/// Summary of a lost drop: who got the name and how close we were.
+pub struct PostMortem {
+    pub username: String,
+    pub release: DateTime<Utc>,
+    pub winner_uuid: Option<String>,
+    pub winner_name: Option<String>,
+    pub lookup: String,
+    pub earliest_arrival_ms: Option<i64>,
+    pub settings: BurstSettings,
+    pub sync_source: String,
+    pub sync_uncertainty_ms: Option<i64>,
+    pub statuses: Vec<String>,
+}
+
+pub fn is_duplicate(attempts: &[AttemptRecord]) -> bool {
+    attempts.iter().any(|a| a.body.contains("DUPLICATE"))
+}
+
+/// Earliest estimated server arrival relative to release, in ms.
+/// Send times are system clock readings, so `clock_offset_ms` (synced minus system) maps them onto server time.
+pub fn earliest_arrival_ms(attempts: &[AttemptRecord], release: DateTime<Utc>, clock_offset_ms: i64) -> Option<i64> {
+    attempts
+        .iter()
+        .filter_map(|a| {
+            let responded = a.responded?;
+            let rtt = responded.timestamp_millis() - a.sent.timestamp_millis();
+            Some(a.sent.timestamp_millis() + clock_offset_ms + rtt / 2 - release.timestamp_millis())
+        })
+        .min()
+}
+
+impl PostMortem {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn build(
+        client: &Client,
+        limiter: &RateLimiter,
+        username: &str,
+        release: DateTime<Utc>,
+        attempts: &[AttemptRecord],
+        clock_offset_ms: i64,
+        settings: BurstSettings,
+        sync_source: &str,
+        sync_uncertainty_ms: Option<i64>,
+    ) -> Self {
+        let (winner_uuid, winner_name, lookup) = match mojang::profile_by_name(client, limiter, username).await {
+            Ok(resp) if resp.status().is_success() => {
+                let profile: serde_json::Value = resp.json().await.unwrap_or_default();
+                (
+                    profile["id"].as_str().map(str::to_string),
+                    profile["name"].as_str().map(str::to_string),
+                    "owner found".to_string(),
+                )
+            }
+            Ok(resp) if resp.status() == reqwest::StatusCode::NO_CONTENT || resp.status() == reqwest::StatusCode::NOT_FOUND => {
+                (None, None, "no current owner".to_string())
+            }
+            Ok(resp) => (None, None, format!("lookup returned {}", resp.status())),
+            Err(e) => (None, None, format!("lookup failed: {}", e)),
+        };
+
+        let statuses = attempts
+            .iter()
+            .map(|a| match (a.status, &a.error) {
+                (Some(status), _) => format!("#{:02}: {}", a.attempt, status),
+                (None, Some(error)) => format!("#{:02}: {}", a.attempt, error),
+                (None, None) => format!("#{:02}: no response", a.attempt),
+            })
+            .collect();
+
+        Self {
+            username: username.to_string(),
+            release,
+            winner_uuid,
+            winner_name,
+            lookup,
+            earliest_arrival_ms: earliest_arrival_ms(attempts, release, clock_offset_ms),
+            settings,
+            sync_source: sync_source.to_string(),
+            sync_uncertainty_ms,
+            statuses,
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "Winner: {} | Our earliest arrival: {} | Offset {}ms, burst {}x{}ms | Sync: {} ±{}",
+            self.winner_uuid.as_deref().unwrap_or(&self.lookup),
+            self.earliest_arrival_ms
+                .map(|ms| format!("{:+}ms vs release", ms))
+                .unwrap_or_else(|| "unknown".to_string()),
+            self.settings.ms_offset,
+            self.settings.burst_size,
+            self.settings.spacing_ms,
+            self.sync_source,
+            self.sync_uncertainty_ms
+                .map(|ms| format!("{}ms", ms))
+                .unwrap_or_else(|| "?".to_string()),
+        )
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "username": self.username,
+            "release": self.release.to_rfc3339(),
+            "winner_uuid": self.winner_uuid,
+            "winner_name": self.winner_name,
+            "lookup": self.lookup,
+            "earliest_arrival_ms": self.earliest_arrival_ms,
+            "ms_offset": self.settings.ms_offset,
+            "burst_size": self.settings.burst_size,
+            "burst_spacing_ms": self.settings.spacing_ms,
+            "window_ms": self.settings.window_ms,
+            "sync_source": self.sync_source,
+            "sync_uncertainty_ms": self.sync_uncertainty_ms,
+            "attempts": self.statuses,
+        })
+    }
+
+    /// Writes the entry as `<release>_<username>.postmortem.json` into `dir`.
+    pub fn save(&self, dir: &Path) -> std::io::Result<PathBuf> {
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(format!("{}_{}.postmortem.json", self.release.format("%Y%m%dT%H%M%S"), mission_log::sanitize(&self.username)));
+        let json = serde_json::to_string_pretty(&self.to_json()).map_err(std::io::Error::other)?;
+        std::fs::write(&path, json)?;
+        Ok(path)
+    }
+}
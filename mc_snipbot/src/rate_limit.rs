@@ -0,0 +1,236 @@
+use chrono::{DateTime, Utc};
+use reqwest::{RequestBuilder, Response, StatusCode};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Assumed per-account budget for the Minecraft services API. Adjustable from the GUI.
+pub const DEFAULT_BUDGET: Budget = Budget {
+    requests: 8,
+    window: Duration::from_secs(60),
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Budget {
+    pub requests: usize,
+    pub window: Duration,
+}
+
+#[derive(Default)]
+struct Bucket {
+    sent: VecDeque<Instant>,
+    blocked_until: Option<Instant>,
+}
+
+impl Bucket {
+    fn prune(&mut self, now: Instant, window: Duration) {
+        while let Some(first) = self.sent.front() {
+            if now.saturating_duration_since(*first) >= window {
+                self.sent.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+pub enum LimitError {
+    /// The limiter refused the request; retry after the given delay.
+    Throttled(Duration),
+    Network(reqwest::Error),
+}
+
+impl std::fmt::Display for LimitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LimitError::Throttled(wait) => write!(f, "rate limited, retry in {}ms", wait.as_millis()),
+            LimitError::Network(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// Sliding-window limiter shared by every outgoing Mojang request, one bucket per account.
+pub struct RateLimiter {
+    budget: Mutex<Budget>,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_BUDGET)
+    }
+}
+
+impl RateLimiter {
+    pub fn new(budget: Budget) -> Self {
+        Self {
+            budget: Mutex::new(budget),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn budget(&self) -> Budget {
+        *self.budget.lock().unwrap()
+    }
+
+    pub fn set_budget(&self, budget: Budget) {
+        *self.budget.lock().unwrap() = budget;
+    }
+
+    /// Number of requests `account` may send at `at`, counting only what has already been sent.
+    pub fn permits_at(&self, account: &str, at: Instant) -> usize {
+        let budget = self.budget();
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets.entry(account.to_string()).or_default();
+        if bucket.blocked_until.is_some_and(|until| at < until) {
+            return 0;
+        }
+        let used = bucket
+            .sent
+            .iter()
+            .filter(|sent| at.saturating_duration_since(**sent) < budget.window)
+            .count();
+        budget.requests.saturating_sub(used)
+    }
+
+    /// Remaining Retry-After block for `account`, if a 429 is still in effect.
+    pub fn blocked_for(&self, account: &str) -> Option<Duration> {
+        let buckets = self.buckets.lock().unwrap();
+        let until = buckets.get(account)?.blocked_until?;
+        until.checked_duration_since(Instant::now())
+    }
+
+    /// Reserves one request slot without waiting.
+    pub fn try_acquire(&self, account: &str) -> Result<(), Duration> {
+        let budget = self.budget();
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets.entry(account.to_string()).or_default();
+        if let Some(until) = bucket.blocked_until {
+            if now < until {
+                return Err(until - now);
+            }
+            bucket.blocked_until = None;
+        }
+        bucket.prune(now, budget.window);
+        if bucket.sent.len() >= budget.requests {
+            let oldest = *bucket.sent.front().unwrap_or(&now);
+            return Err((oldest + budget.window).saturating_duration_since(now));
+        }
+        bucket.sent.push_back(now);
+        Ok(())
+    }
+
+    /// Reserves one request slot, sleeping until the limiter allows it.
+    pub async fn acquire(&self, account: &str) {
+        while let Err(wait) = self.try_acquire(account) {
+            tokio::time::sleep(wait.max(Duration::from_millis(1))).await;
+        }
+    }
+
+    fn record_response(&self, account: &str, response: &Response) {
+        if response.status() != StatusCode::TOO_MANY_REQUESTS {
+            return;
+        }
+        let wait = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| parse_retry_after(value, Utc::now()))
+            .unwrap_or(self.budget().window);
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets.entry(account.to_string()).or_default();
+        bucket.blocked_until = Some(Instant::now() + wait);
+    }
+
+    /// Sends `request` if the account has budget left, never waiting.
+    pub async fn send_now(&self, account: &str, request: RequestBuilder) -> Result<Response, LimitError> {
+        self.try_acquire(account).map_err(LimitError::Throttled)?;
+        self.dispatch(account, request).await
+    }
+
+    /// Waits for budget, then sends `request`.
+    pub async fn send(&self, account: &str, request: RequestBuilder) -> Result<Response, LimitError> {
+        self.acquire(account).await;
+        self.dispatch(account, request).await
+    }
+
+    async fn dispatch(&self, account: &str, request: RequestBuilder) -> Result<Response, LimitError> {
+        let response = request.send().await.map_err(LimitError::Network)?;
+        self.record_response(account, &response);
+        Ok(response)
+    }
+}
+
+/// Non-reversible key identifying the account behind a bearer token. `DefaultHasher` is only
+/// consistent within one process, so the key must not be persisted.
+pub fn account_key(access_token: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    access_token.trim().hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Parses a `Retry-After` header: either delta-seconds or an HTTP-date.
+pub fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc);
+    Some(date.signed_duration_since(now).to_std().unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn budget(requests: usize, window: Duration) -> Budget {
+        Budget { requests, window }
+    }
+
+    #[test]
+    fn retry_after_seconds_and_http_date() {
+        let now = DateTime::parse_from_rfc3339("2025-06-01T12:00:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(parse_retry_after("120", now), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after(" 0 ", now), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("Sun, 01 Jun 2025 12:00:30 GMT", now), Some(Duration::from_secs(30)));
+        assert_eq!(parse_retry_after("Sun, 01 Jun 2025 11:59:00 GMT", now), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("soon", now), None);
+        assert_eq!(parse_retry_after("-5", now), None);
+    }
+
+    #[test]
+    fn try_acquire_stops_at_the_budget_per_account() {
+        let limiter = RateLimiter::new(budget(3, Duration::from_secs(60)));
+        for _ in 0..3 {
+            assert!(limiter.try_acquire("a").is_ok());
+        }
+        let wait = limiter.try_acquire("a").unwrap_err();
+        assert!(wait > Duration::from_secs(59) && wait <= Duration::from_secs(60), "{:?}", wait);
+        assert!(limiter.try_acquire("b").is_ok());
+    }
+
+    #[test]
+    fn window_slides() {
+        let limiter = RateLimiter::new(budget(2, Duration::from_millis(50)));
+        assert!(limiter.try_acquire("a").is_ok());
+        assert!(limiter.try_acquire("a").is_ok());
+        assert!(limiter.try_acquire("a").is_err());
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(limiter.try_acquire("a").is_ok());
+    }
+
+    #[test]
+    fn permits_at_counts_only_requests_still_in_the_window() {
+        let limiter = RateLimiter::new(budget(8, Duration::from_secs(60)));
+        let now = Instant::now();
+        assert_eq!(limiter.permits_at("a", now), 8);
+        limiter.try_acquire("a").unwrap();
+        limiter.try_acquire("a").unwrap();
+        assert_eq!(limiter.permits_at("a", Instant::now()), 6);
+        assert_eq!(limiter.permits_at("a", now + Duration::from_secs(61)), 8);
+        assert_eq!(limiter.permits_at("b", now), 8);
+    }
+}
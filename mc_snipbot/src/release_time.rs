@@ -0,0 +1,268 @@
+use chrono::{DateTime, FixedOffset, Local, LocalResult, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+const NAIVE_FORMATS: [&str; 4] = [
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M",
+];
+
+const OFFSET_FORMATS: [&str; 4] = [
+    "%Y-%m-%d %H:%M:%S%.f%:z",
+    "%Y-%m-%d %H:%M:%S%.f%z",
+    "%Y-%m-%dT%H:%M:%S%.f%z",
+    "%Y-%m-%dT%H:%M%:z",
+];
+
+/// Hint shown next to the release time field.
+pub const FORMAT_HINT: &str = "2025-07-14T06:23:54.250 [UTC|local|+02:00|Europe/Paris], unix s/ms, or in 10m";
+
+enum Zone {
+    Utc,
+    Local,
+    Fixed(FixedOffset),
+    Named(Tz),
+}
+
+/// Parses a release instant with millisecond precision.
+///
+/// Accepted forms:
+/// - date and time with optional fraction, followed by an optional zone
+///   (`UTC`/`Z`/`GMT`, `local`, `+02:00`, `UTC+2`, or an IANA name); no zone means UTC
+/// - RFC 3339 / ISO 8601 with an inline offset
+/// - unix timestamps in seconds (optionally fractional) or milliseconds, with an optional `@`
+/// - relative forms such as `in 10m`, `+1h30m` or `in 90s`
+pub fn parse_release(input: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("Release time is empty".to_string());
+    }
+
+    if let Some(relative) = input.strip_prefix("in ").or_else(|| input.strip_prefix('+')) {
+        let millis = parse_relative_ms(relative)?;
+        return chrono::Duration::try_milliseconds(millis)
+            .and_then(|offset| now.checked_add_signed(offset))
+            .ok_or_else(|| format!("Relative time '{}' is out of range", relative.trim()));
+    }
+
+    let unix = input.strip_prefix('@').unwrap_or(input);
+    if !unix.is_empty() && unix.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        return parse_unix(unix);
+    }
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(input) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    for format in OFFSET_FORMATS {
+        if let Ok(dt) = DateTime::parse_from_str(input, format) {
+            return Ok(dt.with_timezone(&Utc));
+        }
+    }
+
+    let (datetime_part, zone) = match input.rsplit_once(char::is_whitespace) {
+        Some((head, tail)) => match parse_zone(tail) {
+            Some(zone) => (head.trim(), zone),
+            None => (input, Zone::Utc),
+        },
+        None => (input, Zone::Utc),
+    };
+
+    let naive = NAIVE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(datetime_part, format).ok())
+        .ok_or_else(|| format!("Unrecognised release time '{}'", input))?;
+
+    match zone {
+        Zone::Utc => Ok(Utc.from_utc_datetime(&naive)),
+        Zone::Local => resolve(Local.from_local_datetime(&naive), "local time"),
+        Zone::Fixed(offset) => resolve(offset.from_local_datetime(&naive), "offset"),
+        Zone::Named(tz) => resolve(tz.from_local_datetime(&naive), tz.name()),
+    }
+}
+
+/// One-line UTC rendering used for previews and logs.
+pub fn describe(dt: DateTime<Utc>) -> String {
+    format!("{} (unix {} ms)", dt.format("%Y-%m-%d %H:%M:%S%.3f UTC"), dt.timestamp_millis())
+}
+
+fn resolve<T: TimeZone>(result: LocalResult<DateTime<T>>, zone: &str) -> Result<DateTime<Utc>, String> {
+    match result {
+        LocalResult::Single(dt) => Ok(dt.with_timezone(&Utc)),
+        LocalResult::Ambiguous(_, _) => Err(format!("Time is ambiguous in {} (DST change) - use an explicit offset", zone)),
+        LocalResult::None => Err(format!("Time does not exist in {} (DST gap)", zone)),
+    }
+}
+
+fn parse_zone(token: &str) -> Option<Zone> {
+    match token.to_ascii_lowercase().as_str() {
+        "utc" | "z" | "gmt" => return Some(Zone::Utc),
+        "local" => return Some(Zone::Local),
+        _ => {}
+    }
+    let offset = token
+        .strip_prefix("UTC")
+        .or_else(|| token.strip_prefix("GMT"))
+        .unwrap_or(token);
+    if offset.starts_with('+') || offset.starts_with('-') {
+        return parse_offset(offset).map(Zone::Fixed);
+    }
+    token.parse::<Tz>().ok().map(Zone::Named)
+}
+
+/// Parses `+2`, `+02`, `+0230` or `+02:30`.
+fn parse_offset(text: &str) -> Option<FixedOffset> {
+    let sign = if text.starts_with('-') { -1 } else { 1 };
+    let digits: String = text[1..].chars().filter(|c| *c != ':').collect();
+    if digits.is_empty() || digits.len() > 4 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let (hours, minutes) = if digits.len() <= 2 {
+        (digits.parse::<i32>().ok()?, 0)
+    } else {
+        let split = digits.len() - 2;
+        (digits[..split].parse::<i32>().ok()?, digits[split..].parse::<i32>().ok()?)
+    };
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+fn parse_unix(text: &str) -> Result<DateTime<Utc>, String> {
+    let (whole, fraction) = text.split_once('.').unwrap_or((text, ""));
+    if whole.is_empty() || fraction.contains('.') {
+        return Err(format!("Invalid unix timestamp '{}'", text));
+    }
+    let value: i64 = whole.parse().map_err(|_| format!("Invalid unix timestamp '{}'", text))?;
+    let millis = if fraction.is_empty() && whole.len() >= 12 {
+        value
+    } else {
+        let fraction_ms: i64 = format!("{:0<3}", &fraction[..fraction.len().min(3)]).parse().unwrap_or(0);
+        value.checked_mul(1000).and_then(|ms| ms.checked_add(fraction_ms))
+            .ok_or_else(|| format!("Unix timestamp '{}' out of range", text))?
+    };
+    DateTime::from_timestamp_millis(millis).ok_or_else(|| format!("Unix timestamp '{}' out of range", text))
+}
+
+/// Parses durations such as `10m`, `1h30m`, `2h 5m 10s` or `1500ms`.
+fn parse_relative_ms(text: &str) -> Result<i64, String> {
+    let compact: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    if compact.is_empty() {
+        return Err("Relative time needs a duration, e.g. 'in 10m'".to_string());
+    }
+    let mut total = 0i64;
+    let mut rest = compact.as_str();
+    while !rest.is_empty() {
+        let digits_end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        if digits_end == 0 {
+            return Err(format!("Invalid relative time '{}'", text));
+        }
+        let value: i64 = rest[..digits_end].parse().map_err(|_| format!("Invalid relative time '{}'", text))?;
+        rest = &rest[digits_end..];
+        let unit_end = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        let factor = match &rest[..unit_end] {
+            "ms" => 1,
+            "s" | "sec" => 1_000,
+            "m" | "min" => 60_000,
+            "h" => 3_600_000,
+            "d" => 86_400_000,
+            unit => return Err(format!("Unknown unit '{}' in relative time", unit)),
+        };
+        total = value.checked_mul(factor)
+            .and_then(|ms| total.checked_add(ms))
+            .ok_or_else(|| format!("Relative time '{}' is out of range", text))?;
+        rest = &rest[unit_end..];
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 7, 14, 6, 0, 0).unwrap()
+    }
+
+    fn ms(input: &str) -> i64 {
+        parse_release(input, now()).unwrap().timestamp_millis()
+    }
+
+    /// 2025-07-14T06:23:54.250Z
+    const EXPECTED_MS: i64 = 1_752_474_234_250;
+
+    #[test]
+    fn naive_date_time_is_utc() {
+        assert_eq!(ms("2025-07-14T06:23:54.250"), EXPECTED_MS);
+        assert_eq!(ms("2025-07-14 06:23:54.25"), EXPECTED_MS);
+        assert_eq!(ms("2025-07-14 06:23"), EXPECTED_MS - 54_250);
+    }
+
+    #[test]
+    fn explicit_zones() {
+        assert_eq!(ms("2025-07-14 06:23:54.250 UTC"), EXPECTED_MS);
+        assert_eq!(ms("2025-07-14 06:23:54.250 Z"), EXPECTED_MS);
+        assert_eq!(ms("2025-07-14 08:23:54.250 +02:00"), EXPECTED_MS);
+        assert_eq!(ms("2025-07-14 08:23:54.250 UTC+2"), EXPECTED_MS);
+        assert_eq!(ms("2025-07-14 01:53:54.250 -0430"), EXPECTED_MS);
+        assert_eq!(ms("2025-07-14 08:23:54.250 Europe/Paris"), EXPECTED_MS);
+        let local = Local.from_local_datetime(&NaiveDateTime::parse_from_str("2025-07-14 06:23:54.250", "%Y-%m-%d %H:%M:%S%.f").unwrap());
+        if let LocalResult::Single(local) = local {
+            assert_eq!(ms("2025-07-14 06:23:54.250 local"), local.timestamp_millis());
+        }
+    }
+
+    #[test]
+    fn inline_offsets() {
+        assert_eq!(ms("2025-07-14T06:23:54.250Z"), EXPECTED_MS);
+        assert_eq!(ms("2025-07-14T08:23:54.250+02:00"), EXPECTED_MS);
+        assert_eq!(ms("2025-07-14 08:23:54.250+0200"), EXPECTED_MS);
+        assert_eq!(ms("2025-07-14T08:23+02:00"), EXPECTED_MS - 54_250);
+    }
+
+    #[test]
+    fn unix_timestamps() {
+        assert_eq!(ms("1752474234"), EXPECTED_MS - 250);
+        assert_eq!(ms("1752474234.25"), EXPECTED_MS);
+        assert_eq!(ms("@1752474234.250"), EXPECTED_MS);
+        assert_eq!(ms("1752474234250"), EXPECTED_MS);
+        assert!(parse_release("1.2.3", now()).is_err());
+    }
+
+    #[test]
+    fn relative_times() {
+        let base = now().timestamp_millis();
+        assert_eq!(ms("in 10m"), base + 600_000);
+        assert_eq!(ms("+1h30m"), base + 5_400_000);
+        assert_eq!(ms("in 2h 5m 10s"), base + 7_510_000);
+        assert_eq!(ms("in 1500ms"), base + 1_500);
+        assert!(parse_release("in 10y", now()).is_err());
+        assert!(parse_release("in ", now()).is_err());
+    }
+
+    #[test]
+    fn dst_gap_and_overlap_are_rejected() {
+        // Paris clocks jump from 02:00 to 03:00 on 2025-03-30 and repeat 02:00-03:00 on 2025-10-26.
+        let gap = parse_release("2025-03-30 02:30 Europe/Paris", now()).unwrap_err();
+        assert!(gap.contains("DST gap"), "{}", gap);
+        let ambiguous = parse_release("2025-10-26 02:30 Europe/Paris", now()).unwrap_err();
+        assert!(ambiguous.contains("ambiguous"), "{}", ambiguous);
+        assert_eq!(ms("2025-10-26T02:30:00+01:00"), ms("2025-10-26 01:30 UTC"));
+    }
+
+    #[test]
+    fn out_of_range_inputs_fail_instead_of_panicking() {
+        assert!(parse_release("in 99999999d", now()).is_err());
+        assert!(parse_release("in 9223372036854775807ms", now()).is_err());
+        assert!(parse_release("in 9223372036854775807d", now()).is_err());
+        assert!(parse_release("+9223372036854775807ms 1ms", now()).is_err());
+        assert!(parse_release("9223372036854775807.5", now()).is_err());
+        assert!(parse_release("999999999999999999", now()).is_err());
+        assert!(parse_release("99999999999999999999", now()).is_err());
+    }
+
+    #[test]
+    fn garbage_is_rejected() {
+        assert!(parse_release("", now()).is_err());
+        assert!(parse_release("tomorrow", now()).is_err());
+        assert!(parse_release("2025-07-14 06:23 Mars/Olympus", now()).is_err());
+    }
+}
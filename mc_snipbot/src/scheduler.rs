@@ -0,0 +1,141 @@
+use crate::dns::EdgeMode;
+use crate::firing::FiringOptions;
+use crate::mission::TimingProfile;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// How long before release a job's mission is started (pre-flight, time sync, edge probing).
+pub const ARM_LEAD_SECS: i64 = 120;
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JobState {
+    Upcoming,
+    Armed,
+    Finished,
+    Missed,
+    Cancelled,
+}
+
+impl JobState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            JobState::Upcoming => "Upcoming",
+            JobState::Armed => "Armed",
+            JobState::Finished => "Finished",
+            JobState::Missed => "Missed",
+            JobState::Cancelled => "Cancelled",
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: u64,
+    pub username: String,
+    pub release_ms: i64,
+    pub account: String,
+    pub timing: TimingProfile,
+    #[serde(default)]
+    pub edge_mode: EdgeMode,
+    #[serde(default)]
+    pub firing: FiringOptions,
+    pub state: JobState,
+    pub result: Option<String>,
+}
+
+impl Job {
+    /// Unix milliseconds at which the job's mission starts: the lead time before the first
+    /// possible send, which for a drop window is the window's start.
+    pub fn arm_at(&self) -> i64 {
+        self.release_ms - self.timing.window_ms as i64 - self.timing.ms_offset - ARM_LEAD_SECS * 1000
+    }
+
+    /// After this the job can no longer fire: the release, or the end of its drop window.
+    pub fn closes_at(&self) -> i64 {
+        self.release_ms + self.timing.window_ms as i64
+    }
+}
+
+/// Upcoming drops, persisted as JSON so they survive restarts.
+#[derive(Default)]
+pub struct Scheduler {
+    pub jobs: Vec<Job>,
+    path: PathBuf,
+}
+
+impl Scheduler {
+    /// Loads the job file. Jobs that were armed when the app exited are re-queued
+    /// if their release is still ahead, otherwise marked missed.
+    pub fn load(path: PathBuf, now_ms: i64) -> Result<Self, String> {
+        let mut jobs: Vec<Job> = match std::fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text).map_err(|e| format!("Corrupt job file {}: {}", path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(format!("Cannot read {}: {}", path.display(), e)),
+        };
+        for job in jobs.iter_mut().filter(|job| job.state == JobState::Armed) {
+            if job.closes_at() > now_ms {
+                job.state = JobState::Upcoming;
+            } else {
+                job.state = JobState::Missed;
+                job.result = Some("app closed during mission".to_string());
+            }
+        }
+        Ok(Self { jobs, path })
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Cannot create {}: {}", parent.display(), e))?;
+        }
+        let json = serde_json::to_string_pretty(&self.jobs).map_err(|e| e.to_string())?;
+        std::fs::write(&self.path, json).map_err(|e| format!("Cannot write {}: {}", self.path.display(), e))
+    }
+
+    pub fn add(&mut self, username: &str, release_ms: i64, account: &str, timing: TimingProfile, edge_mode: EdgeMode, firing: FiringOptions) -> u64 {
+        let id = self.jobs.iter().map(|job| job.id).max().unwrap_or(0) + 1;
+        self.jobs.push(Job {
+            id,
+            username: username.trim().to_string(),
+            release_ms,
+            account: account.trim().to_string(),
+            timing,
+            edge_mode,
+            firing,
+            state: JobState::Upcoming,
+            result: None,
+        });
+        self.jobs.sort_by_key(|job| job.release_ms);
+        id
+    }
+
+    pub fn remove(&mut self, id: u64) {
+        self.jobs.retain(|job| job.id != id);
+    }
+
+    pub fn set_state(&mut self, id: u64, state: JobState, result: Option<String>) {
+        if let Some(job) = self.jobs.iter_mut().find(|job| job.id == id) {
+            job.state = state;
+            job.result = result;
+        }
+    }
+
+    /// Upcoming jobs inside their arming window, earliest release first.
+    pub fn due(&self, now_ms: i64) -> impl Iterator<Item = &Job> {
+        self.jobs.iter().filter(move |job| {
+            job.state == JobState::Upcoming && job.arm_at() <= now_ms && job.closes_at() > now_ms
+        })
+    }
+
+    /// Marks upcoming jobs whose release has passed without arming. Returns their ids.
+    pub fn expire(&mut self, now_ms: i64) -> Vec<u64> {
+        let mut expired = Vec::new();
+        for job in self.jobs.iter_mut() {
+            if job.state == JobState::Upcoming && job.closes_at() <= now_ms {
+                job.state = JobState::Missed;
+                job.result = Some("release passed before the job could arm".to_string());
+                expired.push(job.id);
+            }
+        }
+        expired
+    }
+}
@@ -0,0 +1,271 @@
+use crate::clock_sync;
+use crate::mission::{self, MissionContext, MissionParams};
+use crate::mission_log::Recorder;
+use crate::paths;
+use crate::rate_limit;
+use crate::logging::{Category, LogEvent};
+use crate::scheduler::JobState;
+use crate::simulator::{self, SimConfig, SimResult};
+use crate::vault::{SecretToken, Vault};
+use chrono::Utc;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+use tokio::runtime::Runtime;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use zeroize::Zeroizing;
+
+/// How often the job keeper looks for jobs to arm.
+const KEEPER_TICK: Duration = Duration::from_millis(250);
+/// While jobs wait, the clock is sampled this often so long waits get a drift fit.
+const CLOCK_KEEPER_INTERVAL: Duration = Duration::from_secs(300);
+
+/// How often the recorder moves events from the log pipeline to the mission files.
+const LOG_PUMP_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Work the GUI asks the background services to do.
+pub enum Command {
+    ProbeEdges,
+    SyncClock,
+    Preflight { access_token: SecretToken, username: String },
+    /// The sender must have claimed `MissionContext::mission_running`; the mission releases it.
+    StartMission(MissionParams),
+    /// Derives the vault key off the GUI thread; `create` makes a new, empty vault.
+    OpenVault { passphrase: Zeroizing<String>, create: bool },
+    /// Runs the Monte Carlo simulator off the GUI thread.
+    Simulate { config: SimConfig, seed: u64 },
+}
+
+/// What the services report back to `SnipeApp::update`.
+pub enum Event {
+    /// The job keeper armed a scheduled job.
+    MissionStarted,
+    MissionFinished,
+    VaultOpened(Result<Vault, String>),
+    Simulated(Result<SimResult, String>),
+    /// A task panicked or could not run; `task` names the command.
+    TaskFailed { task: &'static str, error: String },
+}
+
+type Task = Pin<Box<dyn Future<Output = Option<Event>> + Send>>;
+
+/// The app's single tokio runtime and the channels to and from it.
+pub struct Services {
+    // Dropped last so queued tasks are cancelled together with the app.
+    _runtime: Runtime,
+    commands: UnboundedSender<Command>,
+    events: UnboundedReceiver<Event>,
+}
+
+impl Services {
+    pub fn start(ctx: MissionContext) -> Result<Self, String> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .thread_name("snipebot-worker")
+            .build()
+            .map_err(|e| format!("Cannot start async runtime: {}", e))?;
+        let (commands, mut inbox) = unbounded_channel::<Command>();
+        let (outbox, events) = unbounded_channel();
+
+        runtime.spawn(pump_logs(ctx.recorder.clone()));
+        runtime.spawn(keep_jobs(ctx.clone(), outbox.clone()));
+        runtime.spawn(async move {
+            while let Some(command) = inbox.recv().await {
+                dispatch(command, &ctx, outbox.clone());
+            }
+        });
+
+        Ok(Self {
+            _runtime: runtime,
+            commands,
+            events,
+        })
+    }
+
+    pub fn send(&self, command: Command) {
+        // The dispatcher only stops when the runtime is dropped together with `self`.
+        let _ = self.commands.send(command);
+    }
+
+    /// Events that arrived since the last call.
+    pub fn poll(&mut self) -> Vec<Event> {
+        std::iter::from_fn(|| self.events.try_recv().ok()).collect()
+    }
+}
+
+/// Runs each command as its own task, so a panic ends only that task and is reported.
+fn dispatch(command: Command, ctx: &MissionContext, outbox: UnboundedSender<Event>) {
+    let ctx = ctx.clone();
+    let (task, future): (&'static str, Task) = match command {
+        Command::ProbeEdges => ("edge probe", Box::pin(async move {
+            let api_host = ctx.endpoints.lock().unwrap().api_host_or_default();
+            mission::refresh_edges(&ctx.logs, &ctx.edges, &api_host).await;
+            None
+        })),
+        Command::SyncClock => ("clock sync", Box::pin(async move {
+            let sources = ctx.endpoints.lock().unwrap().time_sources.clone();
+            // A running mission's countdown bounds the round: nothing is applied after its freeze.
+            let remaining = || {
+                let first_send_ms = ctx.countdown.lock().unwrap().as_ref().map(|countdown| countdown.first_send_ms);
+                first_send_ms.map_or(i64::MAX, |ms| ms - ctx.clock.lock().unwrap().estimate.now_ms())
+            };
+            match clock_sync::sync_before_freeze(&ctx.logs, &sources, remaining).await {
+                Ok(sync) => {
+                    if ctx.clock.lock().unwrap().add(&sync) {
+                        *ctx.last_sync.lock().unwrap() = Some(sync);
+                    }
+                }
+                Err(e) => ctx.logs.push(LogEvent::warn(Category::TimeSync, format!("⚠️ Clock sync failed: {}", e))),
+            }
+            None
+        })),
+        Command::Preflight { access_token, username } => ("pre-flight", Box::pin(async move {
+            let endpoints = ctx.endpoints.lock().unwrap().clone();
+            let client = mission::build_snipe_client(&endpoints.api_host_or_default(), None);
+            let account = rate_limit::account_key(&access_token);
+            mission::run_preflight(&client, endpoints.api_base(), &ctx.limiter, &account, &access_token, &username, &ctx.logs).await;
+            None
+        })),
+        Command::StartMission(params) => ("mission", Box::pin(async move {
+            let started = ctx.recorder.lock().unwrap().start_mission(&paths::missions_dir(), &params.username, Utc::now());
+            if let Err(e) = started {
+                ctx.logs.push(LogEvent::warn(Category::Mission, format!("⚠️ Mission log file not created: {}", e)));
+            }
+            let _running = MissionGuard { job: params.job, ctx: ctx.clone() };
+            mission::run(params, ctx).await;
+            Some(Event::MissionFinished)
+        })),
+        Command::OpenVault { passphrase, create } => ("vault", Box::pin(async move {
+            let path = paths::vault_file();
+            let opened = tokio::task::spawn_blocking(move || {
+                if create {
+                    Vault::create(&path, &passphrase)
+                } else {
+                    Vault::unlock(&path, &passphrase)
+                }
+            })
+            .await
+            .unwrap_or_else(|e| Err(e.to_string()));
+            Some(Event::VaultOpened(opened))
+        })),
+        Command::Simulate { config, seed } => ("simulation", Box::pin(async move {
+            let result = tokio::task::spawn_blocking(move || simulator::run(&config, seed))
+                .await
+                .unwrap_or_else(|e| Err(e.to_string()));
+            Some(Event::Simulated(result))
+        })),
+    };
+
+    tokio::spawn(async move {
+        let event = match tokio::spawn(future).await {
+            Ok(event) => event,
+            Err(e) if e.is_panic() => Some(Event::TaskFailed { task, error: panic_message(e.into_panic()) }),
+            Err(e) => Some(Event::TaskFailed { task, error: e.to_string() }),
+        };
+        if let Some(event) = event {
+            let _ = outbox.send(event);
+        }
+    });
+}
+
+/// Arms scheduled jobs on the runtime, so they fire even when the GUI is minimized or
+/// occluded and `update` does not run.
+async fn keep_jobs(ctx: MissionContext, outbox: UnboundedSender<Event>) {
+    let mut ticker = tokio::time::interval(KEEPER_TICK);
+    let mut last_clock_sample: Option<Instant> = None;
+    loop {
+        ticker.tick().await;
+        let now = Utc::now().timestamp_millis();
+        let (waiting, armed) = {
+            let mut scheduler = ctx.scheduler.lock().unwrap();
+            let expired = scheduler.expire(now);
+            for id in &expired {
+                ctx.logs.push(LogEvent::info(Category::Scheduler, format!("⌛ Job #{} missed its release", id)));
+            }
+            let next = {
+                let credentials = ctx.credentials.lock().unwrap();
+                scheduler.due(now).find_map(|job| Some((job.clone(), credentials.token_for(&job.account)?)))
+            };
+            let armed = next.filter(|_| !ctx.mission_running.swap(true, Ordering::SeqCst));
+            if let Some((job, _)) = &armed {
+                scheduler.set_state(job.id, JobState::Armed, None);
+            }
+            if !expired.is_empty() || armed.is_some() {
+                if let Err(e) = scheduler.save() {
+                    ctx.logs.push(LogEvent::warn(Category::Scheduler, format!("⚠️ Could not save scheduled jobs: {}", e)));
+                }
+            }
+            (scheduler.jobs.iter().any(|job| job.state == JobState::Upcoming), armed)
+        };
+
+        if let Some((job, access_token)) = armed {
+            ctx.logs.push(LogEvent::info(Category::Scheduler, format!("⏰ Arming job #{}: '{}' for account '{}'", job.id, job.username, job.account)));
+            *ctx.outcome.lock().unwrap() = None;
+            let _ = outbox.send(Event::MissionStarted);
+            dispatch(Command::StartMission(MissionParams {
+                username: job.username,
+                account: job.account,
+                access_token,
+                release_ms: job.release_ms,
+                timing: job.timing,
+                edge_mode: job.edge_mode,
+                firing: job.firing,
+                job: Some(job.id),
+            }), &ctx, outbox.clone());
+        } else if waiting
+            && !ctx.mission_running.load(Ordering::SeqCst)
+            && last_clock_sample.is_none_or(|at| at.elapsed() > CLOCK_KEEPER_INTERVAL)
+        {
+            last_clock_sample = Some(Instant::now());
+            dispatch(Command::SyncClock, &ctx, outbox.clone());
+        }
+    }
+}
+
+/// Keeps the log pipeline drained whether or not the GUI is drawing.
+async fn pump_logs(recorder: Arc<Mutex<Recorder>>) {
+    let mut ticker = tokio::time::interval(LOG_PUMP_INTERVAL);
+    loop {
+        ticker.tick().await;
+        recorder.lock().unwrap().pump();
+    }
+}
+
+/// Held by a running mission. On drop, also when the mission panics, it files the job's
+/// result, closes the mission's log files and releases `mission_running`.
+struct MissionGuard {
+    job: Option<u64>,
+    ctx: MissionContext,
+}
+
+impl Drop for MissionGuard {
+    fn drop(&mut self) {
+        if let Some(id) = self.job {
+            // A mission that returns always leaves an outcome; none means it panicked.
+            let result = self.ctx.outcome.lock().ok()
+                .and_then(|outcome| outcome.as_ref().map(|outcome| outcome.label()))
+                .unwrap_or_else(|| "crashed".to_string());
+            if let Ok(mut scheduler) = self.ctx.scheduler.lock() {
+                scheduler.set_state(id, JobState::Finished, Some(result));
+                if let Err(e) = scheduler.save() {
+                    self.ctx.logs.push(LogEvent::warn(Category::Scheduler, format!("⚠️ Could not save scheduled jobs: {}", e)));
+                }
+            }
+        }
+        let saved = self.ctx.recorder.lock().ok().and_then(|mut recorder| recorder.finish_mission());
+        if let Some(path) = saved {
+            self.ctx.logs.push(LogEvent::info(Category::Mission, format!("💾 Mission log saved to {}", path.display())));
+        }
+        self.ctx.mission_running.store(false, Ordering::SeqCst);
+    }
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "panicked".to_string())
+}
@@ -0,0 +1,85 @@
+use crate::clock_sync;
+use crate::dns::{self, EdgeMode};
+use crate::drop_calc;
+use crate::firing::FiringOptions;
+use crate::logging::Level;
+use crate::mojang;
+use crate::simulator;
+use serde::{Deserialize, Serialize};
+
+/// Key the settings are stored under in eframe's storage.
+pub const STORAGE_KEY: &str = "snipebot_settings";
+
+/// Servers the app talks to.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct Endpoints {
+    /// Base URL of the Minecraft services API used for name changes and profile checks.
+    pub api_base: String,
+    /// JSON time APIs a sync round queries, in order.
+    pub time_sources: Vec<String>,
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Self {
+            api_base: mojang::API_BASE.to_string(),
+            time_sources: clock_sync::DEFAULT_TIME_SOURCES.iter().map(|url| url.to_string()).collect(),
+        }
+    }
+}
+
+impl Endpoints {
+    pub fn api_base(&self) -> &str {
+        self.api_base.trim().trim_end_matches('/')
+    }
+
+    /// Host of the API, which edge probing resolves and pinning overrides.
+    pub fn api_host(&self) -> Option<String> {
+        let url = reqwest::Url::parse(self.api_base()).ok()?;
+        url.host_str().map(str::to_string)
+    }
+
+    /// `api_host`, falling back to the default host when the base URL does not parse.
+    pub fn api_host_or_default(&self) -> String {
+        self.api_host().unwrap_or_else(|| dns::API_HOST.to_string())
+    }
+}
+
+/// GUI state restored on the next launch. Tokens are never part of it.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub username: String,
+    pub account_name: String,
+    pub ms_offset: i64,
+    pub burst_count: u8,
+    pub drop_window_secs: u32,
+    pub edge_mode: EdgeMode,
+    pub firing: FiringOptions,
+    pub endpoints: Endpoints,
+    pub calc_hold_days: u32,
+    pub sim_hit_window_ms: f64,
+    pub sim_trials: usize,
+    pub log_min_level: Level,
+    pub big_clock_auto: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            username: String::new(),
+            account_name: "main".to_string(),
+            ms_offset: 20,
+            burst_count: 8,
+            drop_window_secs: 0,
+            edge_mode: EdgeMode::Fastest,
+            firing: FiringOptions::default(),
+            endpoints: Endpoints::default(),
+            calc_hold_days: drop_calc::DEFAULT_HOLD_DAYS,
+            sim_hit_window_ms: simulator::DEFAULT_HIT_WINDOW_MS,
+            sim_trials: simulator::DEFAULT_TRIALS,
+            log_min_level: Level::Info,
+            big_clock_auto: true,
+        }
+    }
+}
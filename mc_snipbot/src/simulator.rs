@@ -0,0 +1,209 @@
+use crate::burst;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+pub const DEFAULT_TRIALS: usize = 100_000;
+pub const DEFAULT_HIT_WINDOW_MS: f64 = 50.0;
+
+/// One timing configuration to evaluate.
+#[derive(Clone)]
+pub struct SimConfig {
+    pub ms_offset: i64,
+    /// Send offsets relative to release minus `ms_offset`, as produced by the burst planner.
+    pub offsets_ms: Vec<i64>,
+    pub uncertainty_95_ms: f64,
+    pub rtt_samples_ms: Vec<f64>,
+    pub hit_window_ms: f64,
+    pub trials: usize,
+}
+
+#[derive(Clone)]
+pub struct SimResult {
+    pub config: SimConfig,
+    /// Probability that at least one request arrives in `[release, release + hit_window_ms]`.
+    pub hit_probability: f64,
+    /// Average share of requests that arrive before release and are wasted.
+    pub early_fraction: f64,
+}
+
+impl SimResult {
+    pub fn summary(&self) -> String {
+        format!(
+            "offset {}ms, {} requests, spacing {}ms, clock ±{:.0}ms -> P(hit within {:.0}ms) = {:.1}% ({:.1}% early)",
+            self.config.ms_offset,
+            self.config.offsets_ms.len(),
+            self.spacing_ms(),
+            self.config.uncertainty_95_ms,
+            self.config.hit_window_ms,
+            self.hit_probability * 100.0,
+            self.early_fraction * 100.0
+        )
+    }
+
+    pub fn spacing_ms(&self) -> i64 {
+        let offsets = &self.config.offsets_ms;
+        offsets.get(1).map_or(0, |second| second - offsets[0])
+    }
+}
+
+/// Estimates hit probability by sampling the clock error (normal, 95% bound given) and each
+/// request's one-way latency (half of an RTT drawn from the measured samples).
+pub fn run(config: &SimConfig, seed: u64) -> Result<SimResult, String> {
+    if config.rtt_samples_ms.is_empty() {
+        return Err("No RTT samples - probe the edges or enter RTTs".to_string());
+    }
+    if config.offsets_ms.is_empty() || config.trials == 0 {
+        return Err("Nothing to simulate".to_string());
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let sigma = config.uncertainty_95_ms.max(0.0) / 1.96;
+    let mut hits = 0usize;
+    let mut early = 0usize;
+
+    for _ in 0..config.trials {
+        // Our clock reads `clock_error` ms ahead of the server, so we send that much early.
+        let clock_error = sigma * standard_normal(&mut rng);
+        let mut hit = false;
+        for offset in &config.offsets_ms {
+            let send = (offset - config.ms_offset) as f64 - clock_error;
+            let rtt = config.rtt_samples_ms[rng.gen_range(0..config.rtt_samples_ms.len())];
+            let arrival = send + rtt / 2.0;
+            if arrival < 0.0 {
+                early += 1;
+            } else if arrival <= config.hit_window_ms {
+                hit = true;
+            }
+        }
+        if hit {
+            hits += 1;
+        }
+    }
+
+    Ok(SimResult {
+        config: config.clone(),
+        hit_probability: hits as f64 / config.trials as f64,
+        early_fraction: early as f64 / (config.trials * config.offsets_ms.len()) as f64,
+    })
+}
+
+/// Box-Muller transform.
+fn standard_normal(rng: &mut StdRng) -> f64 {
+    let u1: f64 = rng.gen_range(f64::EPSILON..1.0);
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+/// Parses a comma or whitespace separated list of RTTs in ms.
+pub fn parse_rtt_list(text: &str) -> Result<Vec<f64>, String> {
+    text.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            part.parse::<f64>()
+                .ok()
+                .filter(|ms| *ms >= 0.0)
+                .ok_or_else(|| format!("Invalid RTT '{}'", part))
+        })
+        .collect()
+}
+
+pub const CLI_USAGE: &str = "Usage: snipebot simulate --rtt 40,45,52 [--offset 20] [--burst 8] [--spacing MS] \
+[--uncertainty 35] [--window 50] [--trials 100000]
+
+Several --offset values may be given to compare them.";
+
+/// `snipebot simulate ...`: prints the hit probability for each requested offset.
+pub fn run_cli(args: &[String]) -> Result<String, String> {
+    let mut offsets = Vec::new();
+    let mut burst_count = 8usize;
+    let mut spacing: Option<i64> = None;
+    let mut uncertainty_ms = 0.0;
+    let mut hit_window_ms = DEFAULT_HIT_WINDOW_MS;
+    let mut trials = DEFAULT_TRIALS;
+    let mut rtt_samples = Vec::new();
+
+    let mut iter = args.iter();
+    while let Some(flag) = iter.next() {
+        if flag == "--help" || flag == "-h" {
+            return Ok(CLI_USAGE.to_string());
+        }
+        let value = iter.next().ok_or_else(|| format!("Missing value for {}\n\n{}", flag, CLI_USAGE))?;
+        match flag.as_str() {
+            "--offset" => offsets.push(value.parse::<i64>().map_err(|_| invalid(flag, value))?),
+            "--burst" => burst_count = value.parse().map_err(|_| invalid(flag, value))?,
+            "--spacing" => spacing = Some(value.parse().map_err(|_| invalid(flag, value))?),
+            "--uncertainty" => uncertainty_ms = value.parse().map_err(|_| invalid(flag, value))?,
+            "--window" => hit_window_ms = value.parse().map_err(|_| invalid(flag, value))?,
+            "--trials" => trials = value.parse().map_err(|_| invalid(flag, value))?,
+            "--rtt" => rtt_samples.extend(parse_rtt_list(value)?),
+            _ => return Err(format!("Unknown option {}\n\n{}", flag, CLI_USAGE)),
+        }
+    }
+    if offsets.is_empty() {
+        offsets.push(20);
+    }
+
+    let offsets_ms = match spacing {
+        Some(spacing) => {
+            let half_span = spacing * (burst_count as i64 - 1).max(0) / 2;
+            (0..burst_count as i64).map(|i| i * spacing - half_span).collect()
+        }
+        None => burst::plan(burst_count, uncertainty_ms as i64).offsets_ms,
+    };
+
+    let mut lines = Vec::new();
+    for ms_offset in offsets {
+        let config = SimConfig {
+            ms_offset,
+            offsets_ms: offsets_ms.clone(),
+            uncertainty_95_ms: uncertainty_ms,
+            rtt_samples_ms: rtt_samples.clone(),
+            hit_window_ms,
+            trials,
+        };
+        lines.push(run(&config, 0x5EED)?.summary());
+    }
+    Ok(lines.join("\n"))
+}
+
+fn invalid(flag: &str, value: &str) -> String {
+    format!("Invalid value '{}' for {}", value, flag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(uncertainty_95_ms: f64) -> SimConfig {
+        SimConfig {
+            ms_offset: 20,
+            offsets_ms: burst::plan(8, uncertainty_95_ms as i64).offsets_ms,
+            uncertainty_95_ms,
+            rtt_samples_ms: vec![36.0, 40.0, 44.0],
+            hit_window_ms: DEFAULT_HIT_WINDOW_MS,
+            trials: 20_000,
+        }
+    }
+
+    #[test]
+    fn same_seed_same_result() {
+        let config = config(35.0);
+        let first = run(&config, 7).unwrap();
+        let second = run(&config, 7).unwrap();
+        assert_eq!(first.hit_probability, second.hit_probability);
+        assert_eq!(first.early_fraction, second.early_fraction);
+    }
+
+    #[test]
+    fn exact_clock_always_hits() {
+        // Offset 20ms against a one-way latency of 18-22ms lands every burst around release.
+        let result = run(&config(0.0), 1).unwrap();
+        assert_eq!(result.hit_probability, 1.0);
+    }
+
+    #[test]
+    fn empty_inputs_are_rejected() {
+        assert!(run(&SimConfig { rtt_samples_ms: Vec::new(), ..config(0.0) }, 1).is_err());
+        assert!(run(&SimConfig { trials: 0, ..config(0.0) }, 1).is_err());
+    }
+}
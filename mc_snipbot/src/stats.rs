@@ -0,0 +1,73 @@
+use crate::history::MissionRecord;
+use chrono::Timelike;
+
+/// How the stored missions ended.
+#[derive(Default)]
+pub struct OutcomeCounts {
+    pub claimed: usize,
+    pub unconfirmed: usize,
+    pub not_claimed: usize,
+    pub aborted: usize,
+}
+
+impl OutcomeCounts {
+    pub fn fired(&self) -> usize {
+        self.claimed + self.unconfirmed + self.not_claimed
+    }
+
+    /// Share of fired missions that were claimed.
+    pub fn success_rate(&self) -> Option<f64> {
+        (self.fired() > 0).then(|| self.claimed as f64 / self.fired() as f64)
+    }
+}
+
+pub fn outcomes(records: &[MissionRecord]) -> OutcomeCounts {
+    let mut counts = OutcomeCounts::default();
+    for record in records {
+        match record.outcome.as_str() {
+            "claimed" => counts.claimed += 1,
+            "unconfirmed" => counts.unconfirmed += 1,
+            "aborted" => counts.aborted += 1,
+            _ => counts.not_claimed += 1,
+        }
+    }
+    counts
+}
+
+/// Counts per `width`-ms bin, as (bin start, count), covering the values' range without gaps.
+pub fn histogram(values: &[i64], width: i64) -> Vec<(i64, usize)> {
+    let (Some(min), Some(max)) = (values.iter().min(), values.iter().max()) else {
+        return Vec::new();
+    };
+    let first = min.div_euclid(width);
+    let mut bins = vec![0usize; (max.div_euclid(width) - first + 1) as usize];
+    for value in values {
+        bins[(value.div_euclid(width) - first) as usize] += 1;
+    }
+    bins.into_iter().enumerate().map(|(i, count)| ((first + i as i64) * width, count)).collect()
+}
+
+/// Bin width giving roughly twenty bins over `span_ms`, rounded to 1, 2 or 5 times a power of ten.
+pub fn bin_width(span_ms: i64) -> i64 {
+    let raw = (span_ms.max(1) as f64 / 20.0).max(1.0);
+    let magnitude = 10f64.powf(raw.log10().floor());
+    [1.0, 2.0, 5.0, 10.0].iter().map(|m| m * magnitude).find(|width| *width >= raw).unwrap_or(raw) as i64
+}
+
+/// Median round trip per UTC hour the requests were sent in, as (hour, median ms, samples).
+pub fn rtt_by_hour(records: &[MissionRecord]) -> Vec<(u32, i64, usize)> {
+    let mut by_hour: Vec<Vec<i64>> = vec![Vec::new(); 24];
+    for attempt in records.iter().flat_map(|record| &record.attempts) {
+        if let Some(responded) = attempt.responded {
+            by_hour[attempt.sent.hour() as usize].push(responded.timestamp_millis() - attempt.sent.timestamp_millis());
+        }
+    }
+    by_hour.into_iter()
+        .enumerate()
+        .filter(|(_, rtts)| !rtts.is_empty())
+        .map(|(hour, mut rtts)| {
+            rtts.sort_unstable();
+            (hour as u32, rtts[rtts.len() / 2], rtts.len())
+        })
+        .collect()
+}
@@ -0,0 +1,76 @@
+use crate::history::MissionRecord;
+use crate::mission::AttemptRecord;
+use eframe::egui::{self, Color32};
+use egui_plot::{Line, Plot, PlotPoint, Points, VLine};
+
+const PLANNED: Color32 = Color32::GRAY;
+const SENT: Color32 = Color32::from_rgb(90, 160, 230);
+
+/// Result colour of one request.
+fn attempt_color(attempt: &AttemptRecord) -> Color32 {
+    match attempt.status {
+        Some(status) if (200..300).contains(&status) => Color32::GREEN,
+        Some(429) => Color32::from_rgb(255, 165, 0),
+        Some(_) if attempt.body.contains("DUPLICATE") => Color32::from_rgb(255, 80, 80),
+        Some(_) => Color32::YELLOW,
+        None => Color32::DARK_GRAY,
+    }
+}
+
+/// Hover text of the item under the cursor: its name when it has one, the bare coordinates
+/// otherwise.
+pub fn hover_label(name: &str, value: &PlotPoint) -> String {
+    if name.is_empty() {
+        format!("{:.0}, {:.1}", value.x, value.y)
+    } else {
+        name.to_string()
+    }
+}
+
+/// One row per request with release at zero: planned send, actual send, estimated arrival
+/// (send + RTT/2) and response, all on the synced clock.
+pub fn show(ui: &mut egui::Ui, record: &MissionRecord) {
+    let relative = |ms: i64| (ms - record.release_ms) as f64;
+    let rows = record.attempts.len() as f32;
+    Plot::new(("timeline", record.id))
+        .height((rows * 18.0 + 60.0).clamp(120.0, 360.0))
+        .x_axis_label("ms relative to release (synced clock)")
+        .y_axis_label("attempt")
+        .include_y(0.0)
+        .include_y(record.attempts.len() as f64 + 1.0)
+        .allow_scroll(false)
+        .label_formatter(hover_label)
+        .show(ui, |plot_ui| {
+            plot_ui.vline(VLine::new(0.0).color(Color32::GREEN).name("release"));
+            for attempt in &record.attempts {
+                let row = attempt.attempt as f64;
+                let color = attempt_color(attempt);
+                let planned = relative(attempt.planned.timestamp_millis());
+                let sent = relative(attempt.sent.timestamp_millis() + record.clock_offset_ms);
+                plot_ui.line(Line::new(vec![[planned, row], [sent, row]]).color(PLANNED));
+                plot_ui.points(Points::new([planned, row]).color(PLANNED).radius(3.0)
+                    .name(format!("#{} planned {:+.0}ms", attempt.attempt, planned)));
+                plot_ui.points(Points::new([sent, row]).color(SENT).radius(3.5)
+                    .name(format!("#{} sent {:+.0}ms ({:+.0}ms vs plan)", attempt.attempt, sent, sent - planned)));
+                if let Some(responded) = attempt.responded {
+                    let responded = relative(responded.timestamp_millis() + record.clock_offset_ms);
+                    let arrival = sent + (responded - sent) / 2.0;
+                    plot_ui.line(Line::new(vec![[sent, row], [responded, row]]).color(color));
+                    plot_ui.points(Points::new([arrival, row]).color(color).radius(5.0)
+                        .name(format!("#{} est. arrival {:+.0}ms - {}", attempt.attempt, arrival, attempt.result_label())));
+                    plot_ui.points(Points::new([responded, row]).color(color).radius(3.0)
+                        .name(format!("#{} response {:+.0}ms, RTT {:.0}ms", attempt.attempt, responded, responded - sent)));
+                }
+            }
+        });
+    ui.horizontal_wrapped(|ui| {
+        ui.colored_label(PLANNED, "● planned");
+        ui.colored_label(SENT, "● sent");
+        ui.label("⬤ est. arrival, ● response:");
+        ui.colored_label(Color32::GREEN, "success");
+        ui.colored_label(Color32::from_rgb(255, 80, 80), "duplicate");
+        ui.colored_label(Color32::from_rgb(255, 165, 0), "rate limited");
+        ui.colored_label(Color32::YELLOW, "other status");
+        ui.colored_label(Color32::DARK_GRAY, "no response");
+    });
+}
@@ -0,0 +1,70 @@
+use crate::history::MissionRecord;
+
+/// How many recent missions on the same network feed a recommendation.
+const HISTORY_WINDOW: usize = 10;
+/// Spacing the recommended burst aims for when spreading over RTT jitter and clock error.
+const TARGET_SPACING_MS: f64 = 5.0;
+
+/// Suggested timing for the next mission on a network.
+pub struct Recommendation {
+    pub ms_offset: i64,
+    pub burst_count: usize,
+    pub missions: usize,
+    pub rtt_ms: i64,
+    pub send_lag_ms: i64,
+    pub jitter_ms: i64,
+}
+
+impl Recommendation {
+    pub fn summary(&self) -> String {
+        format!(
+            "offset {}ms, burst {} (from {} missions: RTT {}ms, send lag {:+}ms, RTT jitter {}ms)",
+            self.ms_offset, self.burst_count, self.missions, self.rtt_ms, self.send_lag_ms, self.jitter_ms
+        )
+    }
+}
+
+/// Most recent fired missions on `network`, newest first; `records` comes from `history::load`.
+pub fn recent<'a>(records: &'a [MissionRecord], network: &'a str) -> impl Iterator<Item = &'a MissionRecord> {
+    records.iter().filter(move |record| record.network == network && record.fired()).take(HISTORY_WINDOW)
+}
+
+/// Recommends an offset that lands the first request on release: half the median RTT plus the
+/// firing path's median lateness. The burst is sized so the plan spreads over the clock
+/// uncertainty plus half the RTT jitter at about `TARGET_SPACING_MS`, capped at `max_burst`.
+pub fn recommend(records: &[MissionRecord], network: &str, uncertainty_95_ms: i64, max_burst: usize) -> Option<Recommendation> {
+    let recent: Vec<&MissionRecord> = recent(records, network).collect();
+    let mut rtts: Vec<i64> = recent.iter().filter_map(|record| record.median_rtt_ms()).collect();
+    if rtts.is_empty() {
+        return None;
+    }
+    let mut lags: Vec<i64> = recent.iter().filter_map(|record| record.send_lag_ms()).collect();
+    rtts.sort_unstable();
+    let jitter_ms = percentile(&rtts, 0.9) - percentile(&rtts, 0.1);
+    let rtt_ms = median(&mut rtts)?;
+    let send_lag_ms = median(&mut lags).unwrap_or(0);
+
+    let half_spread = uncertainty_95_ms.max(0) as f64 + jitter_ms as f64 / 2.0;
+    let burst_count = ((2.0 * half_spread / TARGET_SPACING_MS).ceil() as usize + 1).clamp(1, max_burst.max(1));
+
+    Some(Recommendation {
+        ms_offset: (rtt_ms as f64 / 2.0).round() as i64 + send_lag_ms,
+        burst_count,
+        missions: recent.len(),
+        rtt_ms,
+        send_lag_ms,
+        jitter_ms,
+    })
+}
+
+fn median(values: &mut [i64]) -> Option<i64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    Some(values[values.len() / 2])
+}
+
+fn percentile(sorted: &[i64], p: f64) -> i64 {
+    sorted[((sorted.len() - 1) as f64 * p).round() as usize]
+}
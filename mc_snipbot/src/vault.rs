@@ -0,0 +1,305 @@
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+const FORMAT_VERSION: u32 = 1;
+/// Argon2id cost for new vaults: 64 MiB, 3 passes, one lane.
+const KDF_MEMORY_KIB: u32 = 64 * 1024;
+const KDF_ITERATIONS: u32 = 3;
+const KDF_PARALLELISM: u32 = 1;
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+/// Shortest passphrase a new vault accepts.
+pub const MIN_PASSPHRASE_LEN: usize = 8;
+
+/// Tokens stored for one account. Wiped from memory when dropped.
+#[derive(Clone, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+pub struct AccountTokens {
+    pub bearer: String,
+    #[serde(default)]
+    pub refresh: Option<String>,
+}
+
+/// A bearer token on its way to a request. Shared rather than copied, and wiped when the
+/// last holder drops it.
+pub type SecretToken = Arc<Zeroizing<String>>;
+
+/// Tokens usable right now. Shared with the background job keeper, which arms jobs
+/// without the GUI.
+#[derive(Default)]
+pub struct Credentials {
+    /// The token typed into the GUI and the account label it was entered under.
+    pub typed: Option<(String, SecretToken)>,
+    /// `None` while locked.
+    pub vault: Option<Vault>,
+}
+
+impl Credentials {
+    /// The typed token when `account` is the one it was entered under, otherwise the vault's.
+    pub fn token_for(&self, account: &str) -> Option<SecretToken> {
+        match &self.typed {
+            Some((typed_account, token)) if typed_account == account => Some(token.clone()),
+            _ => self.vault.as_ref()?.get(account).map(|tokens| Arc::new(Zeroizing::new(tokens.bearer.clone()))),
+        }
+    }
+}
+
+/// Argon2id parameters, stored in the clear next to the ciphertext.
+#[derive(Clone, Serialize, Deserialize)]
+struct KdfParams {
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+    salt: String,
+}
+
+impl KdfParams {
+    /// Bound to the ciphertext as associated data, so the header cannot be swapped.
+    fn associated_data(&self) -> Vec<u8> {
+        format!(
+            "snipebot-vault:v{}:argon2id:{}:{}:{}:{}",
+            FORMAT_VERSION, self.memory_kib, self.iterations, self.parallelism, self.salt
+        )
+        .into_bytes()
+    }
+
+    fn derive_key(&self, passphrase: &str) -> Result<Zeroizing<[u8; KEY_LEN]>, String> {
+        let salt = from_hex(&self.salt).ok_or("Vault header is damaged (salt)")?;
+        let params = Params::new(self.memory_kib, self.iterations, self.parallelism, Some(KEY_LEN))
+            .map_err(|e| format!("Vault header is damaged (KDF parameters: {})", e))?;
+        let mut key = Zeroizing::new([0u8; KEY_LEN]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), &salt, key.as_mut())
+            .map_err(|e| format!("Key derivation failed: {}", e))?;
+        Ok(key)
+    }
+}
+
+/// On-disk layout of the vault file.
+#[derive(Serialize, Deserialize)]
+struct VaultFile {
+    version: u32,
+    kdf: KdfParams,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// An unlocked vault. The derived key and every token live only as long as this value;
+/// dropping it (locking) wipes them.
+pub struct Vault {
+    path: PathBuf,
+    kdf: KdfParams,
+    key: Zeroizing<[u8; KEY_LEN]>,
+    accounts: BTreeMap<String, AccountTokens>,
+}
+
+impl Vault {
+    /// Creates an empty vault at `path`, refusing to overwrite an existing one.
+    pub fn create(path: &Path, passphrase: &str) -> Result<Self, String> {
+        if path.exists() {
+            return Err(format!("A vault already exists at {}", path.display()));
+        }
+        if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+            return Err(format!("Use a passphrase of at least {} characters", MIN_PASSPHRASE_LEN));
+        }
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let kdf = KdfParams {
+            memory_kib: KDF_MEMORY_KIB,
+            iterations: KDF_ITERATIONS,
+            parallelism: KDF_PARALLELISM,
+            salt: to_hex(&salt),
+        };
+        let vault = Self {
+            path: path.to_path_buf(),
+            key: kdf.derive_key(passphrase)?,
+            kdf,
+            accounts: BTreeMap::new(),
+        };
+        vault.save()?;
+        Ok(vault)
+    }
+
+    /// Decrypts the vault at `path`. A wrong passphrase and a tampered file fail alike.
+    pub fn unlock(path: &Path, passphrase: &str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        let file: VaultFile = serde_json::from_str(&text).map_err(|e| format!("Vault file is damaged: {}", e))?;
+        if file.version != FORMAT_VERSION {
+            return Err(format!("Unsupported vault version {}", file.version));
+        }
+        let key = file.kdf.derive_key(passphrase)?;
+        let nonce = from_hex(&file.nonce).filter(|nonce| nonce.len() == 24).ok_or("Vault file is damaged (nonce)")?;
+        let ciphertext = from_hex(&file.ciphertext).ok_or("Vault file is damaged (ciphertext)")?;
+        let aad = file.kdf.associated_data();
+        let plaintext = Zeroizing::new(
+            XChaCha20Poly1305::new(key.as_ref().into())
+                .decrypt(XNonce::from_slice(&nonce), Payload { msg: &ciphertext, aad: &aad })
+                .map_err(|_| "Wrong passphrase or damaged vault".to_string())?,
+        );
+        let accounts = serde_json::from_slice(&plaintext).map_err(|e| format!("Vault contents are damaged: {}", e))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            kdf: file.kdf,
+            key,
+            accounts,
+        })
+    }
+
+    pub fn get(&self, account: &str) -> Option<&AccountTokens> {
+        self.accounts.get(account)
+    }
+
+    pub fn accounts(&self) -> impl Iterator<Item = (&String, &AccountTokens)> {
+        self.accounts.iter()
+    }
+
+    /// Stores `tokens` for `account` and re-encrypts the file.
+    pub fn set(&mut self, account: &str, tokens: AccountTokens) -> Result<(), String> {
+        self.accounts.insert(account.to_string(), tokens);
+        self.save()
+    }
+
+    pub fn remove(&mut self, account: &str) -> Result<(), String> {
+        self.accounts.remove(account);
+        self.save()
+    }
+
+    /// Encrypts the accounts under a fresh nonce and atomically replaces the file.
+    fn save(&self) -> Result<(), String> {
+        let plaintext = Zeroizing::new(serde_json::to_vec(&self.accounts).map_err(|e| e.to_string())?);
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let aad = self.kdf.associated_data();
+        let ciphertext = XChaCha20Poly1305::new(self.key.as_ref().into())
+            .encrypt(&nonce, Payload { msg: &plaintext, aad: &aad })
+            .map_err(|_| "Encryption failed".to_string())?;
+        let file = VaultFile {
+            version: FORMAT_VERSION,
+            kdf: self.kdf.clone(),
+            nonce: to_hex(&nonce),
+            ciphertext: to_hex(&ciphertext),
+        };
+        let json = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
+        write_private(&self.path, json.as_bytes())
+    }
+}
+
+/// Writes `contents` to a sibling temp file readable only by the owner, then renames it over `path`.
+fn write_private(path: &Path, contents: &[u8]) -> Result<(), String> {
+    use std::io::Write;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Cannot create {}: {}", parent.display(), e))?;
+    }
+    let temp = path.with_extension("tmp");
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(&temp)
+        .and_then(|mut file| file.write_all(contents).and_then(|_| file.sync_all()))
+        .and_then(|_| std::fs::rename(&temp, path))
+        .map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PASSPHRASE: &str = "correct horse battery";
+
+    /// A fresh vault path under the system temp dir, unique per test and process.
+    fn temp_vault(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("snipebot-vault-test-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir.join("tokens.vault")
+    }
+
+    fn tokens(bearer: &str) -> AccountTokens {
+        AccountTokens { bearer: bearer.to_string(), refresh: Some(format!("{}-refresh", bearer)) }
+    }
+
+    #[test]
+    fn round_trip() {
+        let path = temp_vault("round-trip");
+        let mut vault = Vault::create(&path, PASSPHRASE).unwrap();
+        vault.set("main", tokens("token-a")).unwrap();
+        vault.set("alt", tokens("token-b")).unwrap();
+        drop(vault);
+
+        let mut vault = Vault::unlock(&path, PASSPHRASE).unwrap();
+        assert_eq!(vault.get("main").unwrap().bearer, "token-a");
+        assert_eq!(vault.get("alt").unwrap().refresh.as_deref(), Some("token-b-refresh"));
+        vault.remove("alt").unwrap();
+        drop(vault);
+
+        let vault = Vault::unlock(&path, PASSPHRASE).unwrap();
+        assert_eq!(vault.accounts().map(|(account, _)| account.as_str()).collect::<Vec<_>>(), ["main"]);
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(!text.contains("token-a"), "token stored in the clear");
+        assert!(Vault::create(&path, PASSPHRASE).err().unwrap().contains("already exists"));
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn wrong_passphrase_and_tampering_fail() {
+        let path = temp_vault("tamper");
+        Vault::create(&path, PASSPHRASE).unwrap().set("main", tokens("token-a")).unwrap();
+        assert!(Vault::unlock(&path, "incorrect horse").is_err());
+
+        let original = std::fs::read_to_string(&path).unwrap();
+        let mut tampered: VaultFile = serde_json::from_str(&original).unwrap();
+        let last = tampered.ciphertext.pop().unwrap();
+        tampered.ciphertext.push(if last == '0' { '1' } else { '0' });
+        std::fs::write(&path, serde_json::to_string(&tampered).unwrap()).unwrap();
+        assert_eq!(Vault::unlock(&path, PASSPHRASE).err().unwrap(), "Wrong passphrase or damaged vault");
+
+        let mut swapped: VaultFile = serde_json::from_str(&original).unwrap();
+        swapped.nonce = to_hex(&[0u8; 24]);
+        std::fs::write(&path, serde_json::to_string(&swapped).unwrap()).unwrap();
+        assert_eq!(Vault::unlock(&path, PASSPHRASE).err().unwrap(), "Wrong passphrase or damaged vault");
+
+        std::fs::write(&path, &original).unwrap();
+        assert_eq!(Vault::unlock(&path, PASSPHRASE).unwrap().get("main").unwrap().bearer, "token-a");
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn short_passphrase_is_rejected() {
+        let path = temp_vault("short");
+        assert!(Vault::create(&path, "short").is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn typed_token_wins_for_its_account_only() {
+        let credentials = Credentials {
+            typed: Some(("main".to_string(), Arc::new(Zeroizing::new("typed".to_string())))),
+            vault: None,
+        };
+        assert_eq!(credentials.token_for("main").unwrap().as_str(), "typed");
+        assert!(credentials.token_for("alt").is_none());
+    }
+}
@@ -0,0 +1,127 @@
+use crate::rate_limit::Budget;
+
+const MAX_WINDOW_ATTEMPTS: usize = 64;
+
+/// Plans single requests across `nominal_ms ± half_window_ms` (unix ms) for drops whose exact
+/// time is uncertain.
+///
+/// Send times follow the quantiles of a triangular prior peaked at `nominal_ms`, so the most
+/// likely sub-interval gets the densest coverage. The count is the largest one for which no
+/// budget window holds more requests than the limiter allows. `available` is the account's
+/// remaining budget at the window start; earlier sends are assumed to still occupy the first
+/// budget window.
+///
+/// Nothing is planned before `earliest_ms`: a window that is already open is covered from there
+/// on, following the prior's remaining part. Empty once the window has closed, and for an empty
+/// window, which would put every attempt on the same instant.
+pub fn plan(nominal_ms: i64, half_window_ms: i64, earliest_ms: i64, budget: Budget, available: usize) -> Vec<i64> {
+    if half_window_ms <= 0 {
+        return Vec::new();
+    }
+    let first_offset_ms = (earliest_ms - nominal_ms).max(-half_window_ms);
+    if first_offset_ms > half_window_ms {
+        return Vec::new();
+    }
+    let budget_window_ms = (budget.window.as_millis() as i64).max(1);
+    let span_ms = half_window_ms - first_offset_ms;
+    let mut count = (budget.requests * (span_ms / budget_window_ms + 1) as usize).min(MAX_WINDOW_ATTEMPTS);
+    let skipped = triangular_cdf(first_offset_ms as f64, half_window_ms as f64);
+
+    while count > 0 {
+        let times: Vec<i64> = (0..count)
+            .map(|i| {
+                let p = skipped + (1.0 - skipped) * (i as f64 + 0.5) / count as f64;
+                nominal_ms + triangular_quantile(p, half_window_ms as f64) as i64
+            })
+            .collect();
+        if fits_budget(&times, nominal_ms + first_offset_ms, budget_window_ms, budget.requests, available) {
+            return times;
+        }
+        count -= 1;
+    }
+    Vec::new()
+}
+
+/// Inverse CDF of the symmetric triangular distribution on [-half, half].
+fn triangular_quantile(p: f64, half: f64) -> f64 {
+    if p < 0.5 {
+        -half + half * (2.0 * p).sqrt()
+    } else {
+        half - half * (2.0 * (1.0 - p)).sqrt()
+    }
+}
+
+/// CDF of the symmetric triangular distribution on [-half, half].
+fn triangular_cdf(x: f64, half: f64) -> f64 {
+    if half <= 0.0 {
+        return 0.0;
+    }
+    if x < 0.0 {
+        (x + half).powi(2) / (2.0 * half * half)
+    } else {
+        1.0 - (half - x).powi(2) / (2.0 * half * half)
+    }
+}
+
+fn fits_budget(sorted_times: &[i64], start_ms: i64, window_ms: i64, cap: usize, available: usize) -> bool {
+    sorted_times.iter().enumerate().all(|(i, &from)| {
+        let in_window = sorted_times[i..].iter().take_while(|&&t| t < from + window_ms).count();
+        let limit = if from < start_ms + window_ms { available.min(cap) } else { cap };
+        in_window <= limit
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const NOMINAL: i64 = 1_700_000_000_000;
+
+    fn budget(requests: usize) -> Budget {
+        Budget { requests, window: Duration::from_secs(60) }
+    }
+
+    /// Most sends any 60s slice of `times` holds.
+    fn busiest_minute(times: &[i64]) -> usize {
+        times.iter().map(|&from| times.iter().filter(|&&t| t >= from && t < from + 60_000).count()).max().unwrap_or(0)
+    }
+
+    #[test]
+    fn closed_or_empty_window_plans_nothing() {
+        assert!(plan(NOMINAL, 5_000, NOMINAL + 5_001, budget(8), 8).is_empty());
+        assert!(plan(NOMINAL, 0, NOMINAL - 60_000, budget(8), 8).is_empty());
+    }
+
+    #[test]
+    fn open_window_only_schedules_future_sends() {
+        let earliest = NOMINAL - 2_000;
+        let times = plan(NOMINAL, 10_000, earliest, budget(8), 8);
+        assert!(!times.is_empty());
+        assert!(times.iter().all(|&t| t >= earliest), "{:?}", times);
+        assert!(times.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn remaining_budget_is_honoured() {
+        let times = plan(NOMINAL, 10_000, NOMINAL - 60_000, budget(8), 3);
+        assert_eq!(times.len(), 3);
+        assert!(plan(NOMINAL, 10_000, NOMINAL - 60_000, budget(8), 0).is_empty());
+    }
+
+    #[test]
+    fn no_minute_exceeds_the_budget() {
+        for half_window_ms in [1_000, 30_000, 90_000, 300_000] {
+            let times = plan(NOMINAL, half_window_ms, NOMINAL - 600_000, budget(8), 8);
+            assert!(!times.is_empty());
+            assert!(busiest_minute(&times) <= 8, "±{}ms: {:?}", half_window_ms, times);
+        }
+    }
+
+    #[test]
+    fn large_windows_stay_at_the_cap() {
+        let times = plan(NOMINAL, 3_600_000, NOMINAL - 7_200_000, budget(8), 8);
+        assert_eq!(times.len(), MAX_WINDOW_ATTEMPTS);
+        assert!(busiest_minute(&times) <= 8);
+    }
+}